@@ -1,50 +1,324 @@
-use std::num::ParseIntError;
+use std::{cmp::Reverse, collections::BinaryHeap, iter::Enumerate, num::ParseIntError, str::Lines};
 
-fn get_calories(file_contents: &str) -> Result<Vec<u32>, ParseIntError> {
-    if file_contents.is_empty() {
-        return Ok(vec![]);
+use thiserror::Error;
+
+/// Errors when reading the calorie groups. Lines are numbered from 1 and groups
+/// from 0, matching the elf indices.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CaloriesError {
+    #[error("Invalid calories at line {line} (group {group})")]
+    ParseIntError {
+        line: usize,
+        group: usize,
+        source: ParseIntError,
+    },
+
+    #[error("Calories overflow at line {line} (group {group})")]
+    Overflow { line: usize, group: usize },
+}
+
+#[derive(Debug, Error)]
+enum ExecutionError {
+    #[error("Calories error")]
+    CaloriesError(#[from] CaloriesError),
+
+    #[error("Invalid argument")]
+    InvalidArgument(#[from] ParseIntError),
+}
+
+/// Iterator over the total calories carried by each elf, in file order.
+///
+/// Groups are produced lazily, so the whole list never needs to be held in memory.
+struct CalorieGroups<'a> {
+    lines: Enumerate<Lines<'a>>,
+    group: usize,
+    finished: bool,
+}
+
+impl<'a> CalorieGroups<'a> {
+    fn new(file_contents: &'a str) -> Self {
+        Self {
+            lines: file_contents.lines().enumerate(),
+            group: 0,
+            finished: file_contents.is_empty(),
+        }
     }
 
-    let mut calories = vec![0];
+    /// Reads the next group, returning its total. Its items are also pushed onto
+    /// `items` when given, for the statistics that need all of them.
+    fn next_group(&mut self, mut items: Option<&mut Vec<u32>>) -> Result<u32, CaloriesError> {
+        let group = self.group;
+        let mut total: u32 = 0;
+
+        for (i, contents) in self.lines.by_ref() {
+            if contents.is_empty() {
+                self.group += 1;
+                return Ok(total);
+            }
 
-    for line in file_contents.lines() {
-        if line.is_empty() {
-            calories.push(0);
-        } else {
-            let last = calories
-                .last_mut()
-                .expect("Calories vec should never be empty as it is initialized with 0");
+            let line = i + 1;
+            let calories: u32 =
+                contents
+                    .parse()
+                    .map_err(|source| CaloriesError::ParseIntError {
+                        line,
+                        group,
+                        source,
+                    })?;
 
-            *last += line.parse::<u32>()?;
+            total = total
+                .checked_add(calories)
+                .ok_or(CaloriesError::Overflow { line, group })?;
+
+            if let Some(items) = items.as_mut() {
+                items.push(calories);
+            }
         }
+
+        self.finished = true;
+        Ok(total)
     }
 
-    Ok(calories)
+    fn next_with_items(
+        &mut self,
+        items: Option<&mut Vec<u32>>,
+    ) -> Option<Result<u32, CaloriesError>> {
+        if self.finished {
+            return None;
+        }
+
+        let group = self.next_group(items);
+
+        if group.is_err() {
+            self.finished = true;
+        }
+
+        Some(group)
+    }
 }
 
-pub fn solve_part_one(contents: &str) -> Result<Option<u32>, ParseIntError> {
-    let calories = get_calories(contents)?;
+impl Iterator for CalorieGroups<'_> {
+    type Item = Result<u32, CaloriesError>;
 
-    Ok(calories.iter().max().copied())
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_items(None)
+    }
+}
+
+/// Statistics over the items carried by a single elf. For an elf carrying no items,
+/// the mean, median and standard deviation are all 0.
+#[derive(Debug, PartialEq)]
+struct CalorieStats {
+    total: u32,
+    n_items: usize,
+    heaviest: Option<u32>,
+    mean: f64,
+    median: f64,
+    std_dev: f64,
+}
+
+impl CalorieStats {
+    fn new(items: &[u32], total: u32) -> Self {
+        let (mean, median, std_dev) = summarize(items);
+
+        Self {
+            total,
+            n_items: items.len(),
+            heaviest: items.iter().max().copied(),
+            mean,
+            median,
+            std_dev,
+        }
+    }
+}
+
+/// Computes the mean, median and (population) standard deviation of the values.
+///
+/// The mean and variance are accumulated with Welford's method, so that large
+/// calorie counts don't lose precision.
+fn summarize(values: &[u32]) -> (f64, f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let mut mean = 0.0;
+    let mut m2 = 0.0;
+
+    for (i, &value) in values.iter().enumerate() {
+        let value = f64::from(value);
+        let delta = value - mean;
+
+        mean += delta / (i + 1) as f64;
+        m2 += delta * (value - mean);
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+
+    let middle = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (f64::from(sorted[middle - 1]) + f64::from(sorted[middle])) / 2.0
+    } else {
+        f64::from(sorted[middle])
+    };
+
+    (mean, median, (m2 / values.len() as f64).sqrt())
+}
+
+/// Splits the range of values into `n_bins` buckets of equal width, returning the
+/// lower bound of each bucket along with how many values fall into it. Bounds are
+/// computed in `u64`, as the last ones can go past `u32::MAX`.
+fn histogram(values: &[u32], n_bins: usize) -> Vec<(u64, usize)> {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return vec![];
+    };
+
+    let n_bins = n_bins.max(1);
+    let width = u64::from(max - min) / n_bins as u64 + 1;
+
+    let mut counts = vec![0; n_bins];
+    for value in values {
+        counts[(u64::from(value - min) / width) as usize] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| (u64::from(min) + i as u64 * width, count))
+        .collect()
+}
+
+fn get_calories(file_contents: &str) -> Result<Vec<u32>, CaloriesError> {
+    CalorieGroups::new(file_contents).collect()
+}
+
+/// Gets the statistics of every group. Unlike the totals, this needs to hold all
+/// the items of a group at once, so it is kept apart from the solvers.
+fn get_stats(file_contents: &str) -> Result<Vec<CalorieStats>, CaloriesError> {
+    let mut groups = CalorieGroups::new(file_contents);
+    let mut items = Vec::new();
+    let mut stats = Vec::new();
+
+    while let Some(total) = groups.next_with_items(Some(&mut items)) {
+        stats.push(CalorieStats::new(&items, total?));
+        items.clear();
+    }
+
+    Ok(stats)
+}
+
+fn print_stats(file_contents: &str) -> Result<(), CaloriesError> {
+    const N_BINS: usize = 10;
+    const BAR_WIDTH: usize = 50;
+
+    let stats = get_stats(file_contents)?;
+    let totals: Vec<u32> = stats.iter().map(|s| s.total).collect();
+
+    for (index, s) in stats.iter().enumerate() {
+        println!(
+            "Elf {index}: total {}, {} items, heaviest {:?}, mean {:.1}, median {:.1}, std dev {:.1}",
+            s.total, s.n_items, s.heaviest, s.mean, s.median, s.std_dev
+        );
+    }
+
+    let (mean, median, std_dev) = summarize(&totals);
+
+    println!();
+    println!("Elves: {}", stats.len());
+    println!("Items: {}", stats.iter().map(|s| s.n_items).sum::<usize>());
+    println!(
+        "Heaviest item: {:?}",
+        stats.iter().filter_map(|s| s.heaviest).max()
+    );
+    println!("Total calories: mean {mean:.1}, median {median:.1}, std dev {std_dev:.1}");
+    println!();
+
+    let bins = histogram(&totals, N_BINS);
+    let max_count = bins.iter().map(|(_, count)| *count).max().unwrap_or(0);
+
+    for (lower, count) in bins {
+        let bar = "#".repeat(count * BAR_WIDTH / max_count.max(1));
+        println!("{lower:>8} | {bar} {count}");
+    }
+
+    Ok(())
 }
 
-pub fn solve_part_two(contents: &str) -> Result<u32, ParseIntError> {
-    let mut calories = get_calories(contents)?;
+#[derive(Debug, PartialEq, Eq)]
+struct Elf {
+    /// Position of the elf's group in the file, starting at 0.
+    index: usize,
+    calories: u32,
+}
 
-    calories.sort();
+/// Gets the `k` elves carrying the most calories, sorted in decreasing order.
+///
+/// Only a min-heap of size `k` is kept while streaming through the groups. Ties
+/// are broken in favor of the elf that comes first in the file.
+fn top_k(file_contents: &str, k: usize) -> Result<Vec<Elf>, CaloriesError> {
+    // `k` can come straight from the command line, so it is only trusted up to a
+    // point to size the heap.
+    let mut heap = BinaryHeap::with_capacity(k.saturating_add(1).min(1024));
 
-    Ok(calories.iter().rev().take(3).sum())
+    for (index, total) in CalorieGroups::new(file_contents).enumerate() {
+        heap.push(Reverse((total?, Reverse(index))));
+
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((calories, Reverse(index)))| Elf { index, calories })
+        .collect())
 }
 
-fn main() -> Result<(), ParseIntError> {
+pub fn solve_part_one(contents: &str) -> Result<Option<u32>, CaloriesError> {
+    let calories = get_calories(contents)?;
+
+    Ok(calories.iter().max().copied())
+}
+
+/// Sums the three largest totals in `u64`, as each of them can be up to `u32::MAX`.
+pub fn solve_part_two(contents: &str) -> Result<u64, CaloriesError> {
+    Ok(top_k(contents, 3)?
+        .iter()
+        .map(|elf| u64::from(elf.calories))
+        .sum())
+}
+
+fn main() -> Result<(), ExecutionError> {
     // Embeds the content of input.txt inside the final binary, using it as a reference.
     let contents = include_str!("../input.txt");
 
-    let solution1 = solve_part_one(contents)?;
-    println!("{:?}", solution1);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args[..] {
+        ["top", k] => {
+            let k = k.parse()?;
+            let elves = top_k(contents, k)?;
+
+            for elf in &elves {
+                println!("Elf {}: {}", elf.index, elf.calories);
+            }
 
-    let solution2 = solve_part_two(contents)?;
-    println!("{:?}", solution2);
+            println!(
+                "Total: {}",
+                elves.iter().map(|elf| u64::from(elf.calories)).sum::<u64>()
+            );
+        }
+        ["stats"] => print_stats(contents)?,
+        _ => {
+            let solution1 = solve_part_one(contents)?;
+            println!("{:?}", solution1);
+
+            let solution2 = solve_part_two(contents)?;
+            println!("{:?}", solution2);
+        }
+    }
 
     Ok(())
 }
@@ -83,5 +357,96 @@ mod tests {
 
         let expected = 45000;
         assert_eq!(solve_part_two(&file_contents).unwrap(), expected);
+
+        // Three groups of u32::MAX don't overflow the sum.
+        let big = format!("{0}\n\n{0}\n\n{0}", u32::MAX);
+        assert_eq!(solve_part_two(&big).unwrap(), 3 * u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn test_parsing_errors() {
+        assert!(matches!(
+            get_calories("1000\n\n2000\n3x00"),
+            Err(CaloriesError::ParseIntError {
+                line: 4,
+                group: 1,
+                ..
+            })
+        ));
+
+        assert_eq!(
+            get_calories("1\n\n4000000000\n294967295\n1"),
+            Err(CaloriesError::Overflow { line: 5, group: 1 })
+        );
+        assert_eq!(
+            get_calories("4000000000\n294967295").unwrap(),
+            vec![u32::MAX]
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let file_contents =
+            std::fs::read_to_string("test_input.txt").expect("Test file should exist");
+
+        let stats = get_stats(&file_contents).unwrap();
+
+        assert_eq!(
+            stats[0],
+            CalorieStats {
+                total: 6000,
+                n_items: 3,
+                heaviest: Some(3000),
+                mean: 2000.0,
+                median: 2000.0,
+                std_dev: (2_000_000.0f64 / 3.0).sqrt(),
+            }
+        );
+        assert_eq!(stats[2].median, 5500.0);
+        assert_eq!(stats[2].std_dev, 500.0);
+
+        assert_eq!(CalorieStats::new(&[], 0).heaviest, None);
+        assert_eq!(summarize(&[]), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_histogram() {
+        let totals = [6000, 4000, 11000, 24000, 10000];
+
+        assert_eq!(histogram(&totals, 2), vec![(4000, 4), (14001, 1)]);
+        assert_eq!(histogram(&[7, 7], 3), vec![(7, 2), (8, 0), (9, 0)]);
+        assert!(histogram(&[], 3).is_empty());
+
+        // Bounds past the largest total don't overflow.
+        let bins = histogram(&[u32::MAX - 5, u32::MAX], 10);
+        assert_eq!(bins[0], (u64::from(u32::MAX) - 5, 1));
+        assert_eq!(bins[5], (u64::from(u32::MAX), 1));
+        assert_eq!(bins[9].0, u64::from(u32::MAX) + 4);
+    }
+
+    #[test]
+    fn test_top_k() {
+        let file_contents =
+            std::fs::read_to_string("test_input.txt").expect("Test file should exist");
+
+        let expected = vec![
+            Elf {
+                index: 3,
+                calories: 24000,
+            },
+            Elf {
+                index: 2,
+                calories: 11000,
+            },
+        ];
+
+        assert_eq!(top_k(&file_contents, 2).unwrap(), expected);
+        assert_eq!(top_k(&file_contents, 10).unwrap().len(), 5);
+        assert!(top_k(&file_contents, 0).unwrap().is_empty());
+        assert_eq!(top_k(&file_contents, usize::MAX).unwrap().len(), 5);
+
+        // Ties keep the elf that appears first.
+        let top = top_k("1\n\n2\n\n2\n\n1", 1).unwrap();
+        assert_eq!(top.iter().map(|elf| elf.index).collect::<Vec<_>>(), [1]);
     }
 }
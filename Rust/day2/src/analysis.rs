@@ -0,0 +1,251 @@
+use std::{collections::HashMap, convert::Infallible};
+
+use thiserror::Error;
+
+use crate::{
+    cyclic::Move,
+    game::GameResult,
+    guide::{parse_file_contents, ParseError},
+    ruleset::Ruleset,
+};
+
+/// How the second column of the guide is read.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Interpretation {
+    /// As the move to play, like in part one.
+    Choice,
+    /// As the result to reach, like in part two.
+    GameResult,
+}
+
+/// A candidate encoding of the guide, along with the total score it leads to.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mapping {
+    pub interpretation: Interpretation,
+    /// The i-th letter stands for the opponent's i-th move.
+    pub opponent: Vec<char>,
+    /// The i-th letter stands for the player's i-th move or, when read as results,
+    /// for a loss, a draw and a win respectively.
+    pub player: Vec<char>,
+    pub score: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum AnalysisError {
+    #[error("Invalid strategy guide")]
+    ParseError(ParseError),
+
+    /// The search goes through `n! × n!` mappings, which is only reasonable for the
+    /// puzzle's 3 moves.
+    #[error("Mappings can only be inferred for 3-move games, not {0}-move ones")]
+    UnsupportedGame(usize),
+}
+
+impl From<ParseError> for AnalysisError {
+    fn from(error: ParseError) -> Self {
+        AnalysisError::ParseError(error)
+    }
+}
+
+const RESULTS: [GameResult; 3] = [GameResult::Loss, GameResult::Draw, GameResult::Win];
+
+fn raw(c: char) -> Result<char, Infallible> {
+    Ok(c)
+}
+
+/// Best total score achievable by picking, every round, the move scoring the most
+/// against the opponent's. The player's column is ignored, but must be there like
+/// for every other analysis.
+pub fn best_score(contents: &str, ruleset: &Ruleset) -> Result<u32, ParseError> {
+    let n_moves = ruleset.game().n_moves();
+    let guide = parse_file_contents(contents, |c| ruleset.parse_opponent(c), raw)?;
+
+    Ok(guide
+        .iter()
+        .map(|(theirs, _)| {
+            (0..n_moves)
+                .map(|i| ruleset.round_score(*theirs, Move(i)))
+                .max()
+                .unwrap_or(0)
+        })
+        .sum())
+}
+
+/// Expected total score of playing the guide's moves against an opponent that picks
+/// each move with equal probability, ignoring the opponent's column.
+pub fn expected_score_against_random(contents: &str, ruleset: &Ruleset) -> Result<f64, ParseError> {
+    let n_moves = ruleset.game().n_moves();
+    let guide = parse_file_contents(contents, raw, |c| ruleset.parse_player(c))?;
+
+    Ok(guide
+        .iter()
+        .map(|(_, mine)| {
+            let total: u32 = (0..n_moves)
+                .map(|i| ruleset.round_score(Move(i), *mine))
+                .sum();
+
+            total as f64 / n_moves as f64
+        })
+        .sum())
+}
+
+/// Searches every way of assigning the ruleset's letters to moves and to results,
+/// returning the candidates sorted by how close their total score is to `target`.
+/// The first one is the most likely mapping.
+///
+/// Only 3-move games are supported, giving the 6×6 letter permutations of the
+/// puzzle for each interpretation.
+pub fn infer_mapping(
+    contents: &str,
+    ruleset: &Ruleset,
+    target: u32,
+) -> Result<Vec<Mapping>, AnalysisError> {
+    let n_moves = ruleset.game().n_moves();
+
+    if n_moves != RESULTS.len() {
+        return Err(AnalysisError::UnsupportedGame(n_moves));
+    }
+
+    let opponent_letters = ruleset.opponent_letters();
+    let player_letters = ruleset.player_letters();
+
+    // Only how many times each pair of letters shows up matters for the score.
+    let mut counts: HashMap<(usize, usize), u32> = HashMap::new();
+
+    for (a, b) in parse_file_contents(contents, raw, raw)? {
+        let a = opponent_letters.iter().position(|&c| c == a);
+        let b = player_letters.iter().position(|&c| c == b);
+
+        match (a, b) {
+            (Some(a), Some(b)) => *counts.entry((a, b)).or_default() += 1,
+            _ => return Err(ParseError.into()),
+        }
+    }
+
+    let interpretations = [Interpretation::Choice, Interpretation::GameResult];
+
+    let opponent_permutations = permutations(opponent_letters.len());
+    let player_permutations = permutations(player_letters.len());
+
+    let mut mappings = Vec::new();
+
+    for interpretation in interpretations {
+        for opponent in &opponent_permutations {
+            for player in &player_permutations {
+                let score = counts
+                    .iter()
+                    .map(|(&(a, b), count)| {
+                        let theirs = Move(opponent[a]);
+
+                        count
+                            * match interpretation {
+                                Interpretation::Choice => {
+                                    ruleset.round_score(theirs, Move(player[b]))
+                                }
+                                Interpretation::GameResult => {
+                                    let result = RESULTS[player[b]];
+                                    let mine = ruleset
+                                        .game()
+                                        .get_move_to_ensure_result_against(theirs, &result);
+
+                                    ruleset.move_score(mine) + ruleset.outcome_score(&result)
+                                }
+                            }
+                    })
+                    .sum();
+
+                mappings.push(Mapping {
+                    interpretation,
+                    opponent: invert(opponent, &opponent_letters),
+                    player: invert(player, &player_letters),
+                    score,
+                });
+            }
+        }
+    }
+
+    mappings.sort_by_key(|mapping| mapping.score.abs_diff(target));
+
+    Ok(mappings)
+}
+
+/// All permutations of `0..n`, where `permutation[i]` is the move (or result) that
+/// the i-th letter is assigned to.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+
+    permutations(n - 1)
+        .into_iter()
+        .flat_map(|permutation| {
+            (0..n).map(move |i| {
+                let mut permutation = permutation.clone();
+                permutation.insert(i, n - 1);
+                permutation
+            })
+        })
+        .collect()
+}
+
+/// Lists the letters by the move they were assigned to.
+fn invert(permutation: &[usize], letters: &[char]) -> Vec<char> {
+    let mut inverted = vec![' '; letters.len()];
+
+    for (letter, &assigned) in letters.iter().zip(permutation) {
+        inverted[assigned] = *letter;
+    }
+
+    inverted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CONTENT: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn best_and_expected_scores() {
+        let ruleset = Ruleset::default();
+
+        // Paper against Rock, Scissors against Paper and Rock against Scissors.
+        assert_eq!(best_score(TEST_CONTENT, &ruleset).unwrap(), 8 + 9 + 7);
+        assert_eq!(best_score("A X\nB X", &ruleset).unwrap(), 8 + 9);
+        assert!(best_score("A", &ruleset).is_err());
+        assert!(best_score("AQ", &ruleset).is_err());
+        assert!(best_score("A Z extra", &ruleset).is_err());
+
+        // Each move wins, draws and loses once against the three possible moves.
+        let expected = expected_score_against_random(TEST_CONTENT, &ruleset).unwrap();
+        assert_eq!(expected, (2.0 + 1.0 + 3.0) + 3.0 * 3.0);
+    }
+
+    #[test]
+    fn inference() {
+        let ruleset = Ruleset::default();
+        let mappings = infer_mapping(TEST_CONTENT, &ruleset, 12).unwrap();
+
+        assert_eq!(mappings.len(), 72);
+        assert!(mappings.contains(&Mapping {
+            interpretation: Interpretation::GameResult,
+            opponent: vec!['A', 'B', 'C'],
+            player: vec!['X', 'Y', 'Z'],
+            score: 12,
+        }));
+        assert_eq!(mappings[0].score, 12);
+
+        let mappings = infer_mapping(TEST_CONTENT, &ruleset, 15).unwrap();
+        assert_eq!(mappings[0].score, 15);
+
+        assert_eq!(permutations(3).len(), 6);
+        assert!(matches!(
+            infer_mapping("A Q", &ruleset, 0),
+            Err(AnalysisError::ParseError(_))
+        ));
+        assert!(matches!(
+            infer_mapping(TEST_CONTENT, &Ruleset::with_letters(9).unwrap(), 0),
+            Err(AnalysisError::UnsupportedGame(9))
+        ));
+    }
+}
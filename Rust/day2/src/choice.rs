@@ -7,19 +7,7 @@ pub enum Choice {
     Scissors,
 }
 
-impl TryFrom<char> for Choice {
-    type Error = ParseChoiceError;
-    fn try_from(s: char) -> Result<Self, Self::Error> {
-        match s {
-            'A' | 'X' => Ok(Choice::Rock),
-            'B' | 'Y' => Ok(Choice::Paper),
-            'C' | 'Z' => Ok(Choice::Scissors),
-            _ => Err(ParseChoiceError),
-        }
-    }
-}
-
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct ParseChoiceError;
 
 impl From<Choice> for i32 {
@@ -44,14 +32,6 @@ impl From<i32> for Choice {
 }
 
 impl Choice {
-    pub fn score(&self) -> u32 {
-        match self {
-            Choice::Rock => 1,
-            Choice::Paper => 2,
-            Choice::Scissors => 3,
-        }
-    }
-
     pub fn get_choice_to_ensure_result_against(&self, result: &GameResult) -> Choice {
         (i32::from(*self) + i32::from(*result)).into()
     }
@@ -0,0 +1,129 @@
+use crate::game::GameResult;
+
+/// A move in a [`CyclicGame`], identified by its position in the cycle.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Move(pub usize);
+
+/// A generalization of Rock-Paper-Scissors to an odd number of moves arranged in a
+/// cycle, where each move beats the `(n - 1) / 2` moves right before it and loses to
+/// the `(n - 1) / 2` moves right after it.
+///
+/// With 3 moves this is the usual Rock (0), Paper (1), Scissors (2) game.
+///
+/// How moves are written in a guide is up to the [`crate::ruleset::Ruleset`].
+#[derive(Clone, Debug)]
+pub struct CyclicGame {
+    n_moves: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CyclicGameError {
+    EvenNumberOfMoves,
+}
+
+impl CyclicGame {
+    pub fn new(n_moves: usize) -> Result<Self, CyclicGameError> {
+        if n_moves.is_multiple_of(2) {
+            return Err(CyclicGameError::EvenNumberOfMoves);
+        }
+
+        Ok(Self { n_moves })
+    }
+
+    /// The original game: Rock, Paper, Scissors.
+    pub fn rock_paper_scissors() -> Self {
+        Self::new(3).expect("3 moves is a valid game")
+    }
+
+    /// Rock-Paper-Scissors-Lizard-Spock, ordered as Rock, Spock, Paper, Lizard,
+    /// Scissors so that the cyclic rule matches the usual one.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::new(5).expect("5 moves is a valid game")
+    }
+
+    pub fn n_moves(&self) -> usize {
+        self.n_moves
+    }
+
+    pub fn score(&self, m: Move) -> u32 {
+        m.0 as u32 + 1
+    }
+
+    pub fn get_move_to_ensure_result_against(&self, theirs: Move, result: &GameResult) -> Move {
+        let offset = match result {
+            GameResult::Draw => 0,
+            GameResult::Win => 1,
+            GameResult::Loss => self.n_moves - 1,
+        };
+
+        Move((theirs.0 + offset) % self.n_moves)
+    }
+
+    pub fn pit_against(&self, mine: Move, theirs: Move) -> GameResult {
+        let difference = (mine.0 + self.n_moves - theirs.0) % self.n_moves;
+
+        match difference {
+            0 => GameResult::Draw,
+            d if d <= self.n_moves / 2 => GameResult::Win,
+            _ => GameResult::Loss,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::choice::Choice;
+
+    #[test]
+    fn matches_choice() {
+        let game = CyclicGame::rock_paper_scissors();
+        let choices = [Choice::Rock, Choice::Paper, Choice::Scissors];
+
+        for (i, mine) in choices.iter().enumerate() {
+            for (j, theirs) in choices.iter().enumerate() {
+                assert_eq!(
+                    i32::from(game.pit_against(Move(i), Move(j))),
+                    i32::from(mine.pit_against(theirs))
+                );
+            }
+
+            for result in [GameResult::Win, GameResult::Draw, GameResult::Loss] {
+                let expected = mine.get_choice_to_ensure_result_against(&result);
+
+                assert_eq!(
+                    game.get_move_to_ensure_result_against(Move(i), &result).0 as i32,
+                    i32::from(expected)
+                );
+            }
+
+            assert_eq!(game.score(Move(i)), i as u32 + 1);
+        }
+    }
+
+    #[test]
+    fn lizard_spock() {
+        let game = CyclicGame::rock_paper_scissors_lizard_spock();
+        let (rock, spock, paper, lizard, scissors) = (Move(0), Move(1), Move(2), Move(3), Move(4));
+
+        assert!(matches!(game.pit_against(spock, rock), GameResult::Win));
+        assert!(matches!(game.pit_against(paper, rock), GameResult::Win));
+        assert!(matches!(game.pit_against(lizard, spock), GameResult::Win));
+        assert!(matches!(
+            game.pit_against(scissors, lizard),
+            GameResult::Win
+        ));
+        assert!(matches!(game.pit_against(rock, scissors), GameResult::Win));
+        assert!(matches!(game.pit_against(rock, lizard), GameResult::Win));
+        assert!(matches!(game.pit_against(lizard, paper), GameResult::Win));
+        assert!(matches!(game.pit_against(rock, paper), GameResult::Loss));
+    }
+
+    #[test]
+    fn invalid_games() {
+        assert_eq!(
+            CyclicGame::new(4).unwrap_err(),
+            CyclicGameError::EvenNumberOfMoves
+        );
+    }
+}
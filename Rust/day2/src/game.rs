@@ -1,4 +1,4 @@
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum GameResult {
     Win,
     Draw,
@@ -26,26 +26,5 @@ impl From<GameResult> for i32 {
     }
 }
 
-impl TryFrom<char> for GameResult {
-    type Error = ParseGameResultError;
-    fn try_from(s: char) -> Result<Self, Self::Error> {
-        match s {
-            'X' => Ok(GameResult::Loss),
-            'Y' => Ok(GameResult::Draw),
-            'Z' => Ok(GameResult::Win),
-            _ => Err(ParseGameResultError),
-        }
-    }
-}
-
+#[derive(Debug, PartialEq, Eq)]
 pub struct ParseGameResultError;
-
-impl GameResult {
-    pub fn score(&self) -> u32 {
-        match self {
-            GameResult::Win => 6,
-            GameResult::Draw => 3,
-            GameResult::Loss => 0,
-        }
-    }
-}
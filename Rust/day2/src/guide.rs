@@ -0,0 +1,26 @@
+// In a real program, we could use the thiserror crate to create better error types.
+#[derive(Debug)]
+pub struct ParseError;
+
+// Unites the parsing of both columns of the guide using generics.
+pub fn parse_file_contents<T, U, E, F>(
+    contents: &str,
+    parse_first: impl Fn(char) -> Result<T, E>,
+    parse_second: impl Fn(char) -> Result<U, F>,
+) -> Result<Vec<(T, U)>, ParseError> {
+    contents
+        .lines()
+        .map(|line| match line.as_bytes() {
+            [a, _, b] => {
+                let first = parse_first(*a as char);
+                let second = parse_second(*b as char);
+
+                match (first, second) {
+                    (Ok(first), Ok(second)) => Ok((first, second)),
+                    _ => Err(ParseError),
+                }
+            }
+            _ => Err(ParseError),
+        })
+        .collect()
+}
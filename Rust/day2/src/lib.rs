@@ -1,2 +1,7 @@
+pub mod analysis;
 pub mod choice;
+pub mod cyclic;
 pub mod game;
+pub mod guide;
+pub mod ruleset;
+pub mod tournament;
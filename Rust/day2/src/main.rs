@@ -1,61 +1,161 @@
-use day2::{choice::Choice, game::GameResult};
+use std::num::ParseIntError;
+
+use day2::{
+    analysis::{best_score, expected_score_against_random, infer_mapping, AnalysisError},
+    guide::{parse_file_contents, ParseError},
+    ruleset::{Ruleset, RulesetError},
+    tournament::{round_robin, BeatLastMove, FixedSequence, FrequencyCounter, Player, Random},
+};
+use thiserror::Error;
 
 const CONTENT: &str = include_str!("../input.txt");
 
-// In a real program, we could use the thiserror crate to create better error types.
-#[derive(Debug)]
-struct ParseError;
-
-// Unites the above parsing functionalities using generics.
-fn parse_file_contents<T, U>(contents: &str) -> Result<Vec<(T, U)>, ParseError>
-where
-    T: TryFrom<char>,
-    U: TryFrom<char>,
-{
-    contents
-        .lines()
-        .map(|line| match line.as_bytes() {
-            [a, _, b] => {
-                let first = T::try_from(*a as char);
-                let second = U::try_from(*b as char);
-
-                match (first, second) {
-                    (Ok(first), Ok(second)) => Ok((first, second)),
-                    _ => Err(ParseError),
-                }
-            }
-            _ => Err(ParseError),
-        })
-        .collect()
+fn solve_part_one(contents: &str, ruleset: &Ruleset) -> Result<u32, ParseError> {
+    let moves = parse_file_contents(
+        contents,
+        |c| ruleset.parse_opponent(c),
+        |c| ruleset.parse_player(c),
+    )?;
+
+    Ok(moves.iter().fold(0, |acc, (their_move, my_move)| {
+        acc + ruleset.round_score(*their_move, *my_move)
+    }))
 }
 
-fn solve_part_one(contents: &str) -> Result<u32, ParseError> {
-    let choices = parse_file_contents::<Choice, Choice>(contents)?;
+fn solve_part_two(contents: &str, ruleset: &Ruleset) -> Result<u32, ParseError> {
+    let moves = parse_file_contents(
+        contents,
+        |c| ruleset.parse_opponent(c),
+        |c| ruleset.parse_result(c),
+    )?;
 
-    Ok(choices.iter().fold(0, |acc, (their_choice, my_choice)| {
-        acc + my_choice.pit_against(their_choice).score() + my_choice.score()
+    Ok(moves.iter().fold(0, |acc, (their_move, intended_result)| {
+        let my_move = ruleset
+            .game()
+            .get_move_to_ensure_result_against(*their_move, intended_result);
+
+        acc + ruleset.move_score(my_move) + ruleset.outcome_score(intended_result)
     }))
 }
 
-fn solve_part_two(contents: &str) -> Result<u32, ParseError> {
-    let choices = parse_file_contents::<Choice, GameResult>(contents)?;
+// Either a number of moves or a path to a TOML/JSON ruleset can be given, defaulting
+// to the puzzle's Rock-Paper-Scissors rules.
+fn get_ruleset(arg: Option<&str>) -> Result<Ruleset, RulesetError> {
+    match arg {
+        Some(arg) => match arg.parse() {
+            Ok(n_moves) => Ruleset::with_letters(n_moves),
+            Err(_) => Ruleset::load(arg),
+        },
+        None => Ok(Ruleset::default()),
+    }
+}
+
+fn analyze(contents: &str, ruleset: &Ruleset, target: u32) -> Result<(), ParseError> {
+    println!("Best score: {}", best_score(contents, ruleset)?);
+    println!(
+        "Expected score against a random opponent: {:.2}",
+        expected_score_against_random(contents, ruleset)?
+    );
+
+    let mappings = match infer_mapping(contents, ruleset, target) {
+        Ok(mappings) => mappings,
+        Err(AnalysisError::ParseError(e)) => return Err(e),
+        Err(e @ AnalysisError::UnsupportedGame(_)) => {
+            println!("{e}");
+            return Ok(());
+        }
+    };
+    let best_distance = mappings[0].score.abs_diff(target);
 
-    Ok(choices
+    for mapping in mappings
         .iter()
-        .fold(0, |acc, (their_choice, intended_result)| {
-            let my_choice = their_choice.get_choice_to_ensure_result_against(intended_result);
+        .take_while(|mapping| mapping.score.abs_diff(target) == best_distance)
+    {
+        println!(
+            "Most likely mapping ({:?}): {:?} -> {:?}, scoring {}",
+            mapping.interpretation, mapping.opponent, mapping.player, mapping.score
+        );
+    }
 
-            acc + my_choice.score() + intended_result.score()
-        }))
+    Ok(())
 }
 
-fn main() -> Result<(), ParseError> {
-    println!("{}", solve_part_one(CONTENT)?);
-    println!("{}", solve_part_two(CONTENT)?);
+fn tournament(contents: &str, rounds: usize, ruleset: &Ruleset) -> Result<(), ParseError> {
+    let mut players: Vec<Box<dyn Player>> = vec![
+        Box::new(FixedSequence::from_guide(
+            "Strategy guide",
+            contents,
+            ruleset,
+        )?),
+        Box::new(Random::new(2022)),
+        Box::new(FrequencyCounter::default()),
+        Box::new(BeatLastMove::default()),
+    ];
+
+    for (i, standing) in round_robin(&mut players, rounds, ruleset)
+        .iter()
+        .enumerate()
+    {
+        println!(
+            "{}. {} - {} points ({}W/{}D/{}L)",
+            i + 1,
+            standing.name,
+            standing.score,
+            standing.wins,
+            standing.draws,
+            standing.losses
+        );
+    }
 
     Ok(())
 }
 
+fn main() -> Result<(), ExecutionError> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args[..] {
+        ["analyze", target, ref rest @ ..] => {
+            let ruleset = get_ruleset(rest.first().copied())?;
+            let target = target.parse()?;
+
+            analyze(CONTENT, &ruleset, target)?;
+        }
+        ["tournament", rounds, ref rest @ ..] => {
+            let ruleset = get_ruleset(rest.first().copied())?;
+            let rounds = rounds.parse()?;
+
+            tournament(CONTENT, rounds, &ruleset)?;
+        }
+        ref rest => {
+            let ruleset = get_ruleset(rest.first().copied())?;
+
+            println!("{}", solve_part_one(CONTENT, &ruleset)?);
+            println!("{}", solve_part_two(CONTENT, &ruleset)?);
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+enum ExecutionError {
+    #[error("Invalid strategy guide")]
+    ParseError(ParseError),
+
+    #[error("Invalid ruleset")]
+    RulesetError(#[from] RulesetError),
+
+    #[error("Error when parsing an argument")]
+    InvalidArgument(#[from] ParseIntError),
+}
+
+impl From<ParseError> for ExecutionError {
+    fn from(error: ParseError) -> Self {
+        ExecutionError::ParseError(error)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,11 +164,51 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        assert_eq!(solve_part_one(TEST_CONTENT).unwrap(), 15);
+        let ruleset = Ruleset::default();
+
+        assert_eq!(solve_part_one(TEST_CONTENT, &ruleset).unwrap(), 15);
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(solve_part_two(TEST_CONTENT).unwrap(), 12);
+        let ruleset = Ruleset::default();
+
+        assert_eq!(solve_part_two(TEST_CONTENT, &ruleset).unwrap(), 12);
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        let ruleset = Ruleset::with_letters(5).unwrap();
+        let guide = "A V\nE W\nB X\nC W";
+
+        // Draw (1 + 3), win (2 + 6), win (3 + 6), loss (2 + 0).
+        assert_eq!(solve_part_one(guide, &ruleset).unwrap(), 23);
+        assert!(solve_part_one(TEST_CONTENT, &ruleset).is_ok());
+        assert!(solve_part_one("A Q", &ruleset).is_err());
+    }
+
+    #[test]
+    fn test_seven_moves() {
+        let ruleset = Ruleset::with_letters(7).unwrap();
+
+        // Scissors-like move 2 against 6: wins, since 2 - 6 = 3 (mod 7).
+        assert_eq!(solve_part_one("G V", &ruleset).unwrap(), 3 + 6);
+        // Loss against move 0 means playing move 6.
+        assert_eq!(solve_part_two("A X", &ruleset).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_ruleset_files() {
+        let ruleset = Ruleset::load("rulesets/default.toml").unwrap();
+
+        assert_eq!(solve_part_one(TEST_CONTENT, &ruleset).unwrap(), 15);
+        assert_eq!(solve_part_two(TEST_CONTENT, &ruleset).unwrap(), 12);
+
+        let ruleset = Ruleset::load("rulesets/lizard_spock.json").unwrap();
+
+        // Rock against Paper is a loss (1 + 0). Paper against Rock, a win (2 + 10).
+        // Scissors against Scissors, a draw (3 + 5).
+        assert_eq!(solve_part_one("C V\nA X\nE Z", &ruleset).unwrap(), 21);
+        assert!(solve_part_one("F V", &ruleset).is_err());
     }
 }
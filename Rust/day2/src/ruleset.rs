@@ -0,0 +1,345 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    choice::ParseChoiceError,
+    cyclic::{CyclicGame, CyclicGameError, Move},
+    game::{GameResult, ParseGameResultError},
+};
+
+/// Scores and letter encodings for a strategy guide. This is the only place where
+/// letters are mapped to moves and results, and where rounds are scored.
+///
+/// The number of moves of the underlying [`CyclicGame`] is given by the number of
+/// move scores.
+#[derive(Clone, Debug)]
+pub struct Ruleset {
+    game: CyclicGame,
+    move_scores: Vec<u32>,
+    outcome_scores: OutcomeScores,
+    opponent: HashMap<char, Move>,
+    player: HashMap<char, Move>,
+    results: HashMap<char, GameResult>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct OutcomeScores {
+    pub win: u32,
+    pub draw: u32,
+    pub loss: u32,
+}
+
+/// Raw layout of a ruleset file, before validation.
+#[derive(Deserialize)]
+struct RulesetFile {
+    move_scores: Vec<u32>,
+    outcome_scores: OutcomeScores,
+    encodings: Encodings,
+}
+
+/// Letters encoding each move, where the i-th letter stands for the i-th move.
+#[derive(Deserialize)]
+struct Encodings {
+    opponent: String,
+    player: String,
+    results: ResultEncodings,
+}
+
+#[derive(Deserialize)]
+struct ResultEncodings {
+    win: char,
+    draw: char,
+    loss: char,
+}
+
+#[derive(Debug, Error)]
+pub enum RulesetError {
+    #[error("Invalid TOML ruleset")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Invalid JSON ruleset")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Could not read ruleset file")]
+    Io(#[from] std::io::Error),
+
+    #[error("Unknown ruleset format, expected a .toml or .json file")]
+    UnknownFormat,
+
+    #[error("Invalid game")]
+    InvalidGame(CyclicGameError),
+
+    #[error("{0} moves don't fit in the alphabet, at most 13 can be encoded with letters")]
+    TooManyMoves(usize),
+
+    #[error("Expected {expected} letters for the {column} column, found {found}")]
+    WrongNumberOfLetters {
+        column: &'static str,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("Letter {0} is used more than once in the same column")]
+    DuplicateLetter(char),
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::with_letters(3).expect("3 moves is a valid game")
+    }
+}
+
+impl Ruleset {
+    /// Builds the puzzle's scoring for a game with `n_moves` moves. The opponent's
+    /// moves are encoded from `A` onwards and ours from the end of the alphabet, so
+    /// that the last move is always `Z`, and results are encoded by `X`, `Y` and `Z`.
+    ///
+    /// Both ranges of moves must fit in the alphabet without overlapping.
+    pub fn with_letters(n_moves: usize) -> Result<Self, RulesetError> {
+        if n_moves > 13 {
+            return Err(RulesetError::TooManyMoves(n_moves));
+        }
+
+        let opponent = (0..n_moves).map(|i| (b'A' + i as u8) as char).collect();
+        let player = (0..n_moves)
+            .map(|i| (b'Z' + 1 - n_moves as u8 + i as u8) as char)
+            .collect();
+
+        RulesetFile {
+            move_scores: (1..=n_moves as u32).collect(),
+            outcome_scores: OutcomeScores {
+                win: 6,
+                draw: 3,
+                loss: 0,
+            },
+            encodings: Encodings {
+                opponent,
+                player,
+                results: ResultEncodings {
+                    win: 'Z',
+                    draw: 'Y',
+                    loss: 'X',
+                },
+            },
+        }
+        .try_into()
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, RulesetError> {
+        toml::from_str::<RulesetFile>(contents)?.try_into()
+    }
+
+    pub fn from_json(contents: &str) -> Result<Self, RulesetError> {
+        serde_json::from_str::<RulesetFile>(contents)?.try_into()
+    }
+
+    /// Loads a ruleset, picking the format from the file extension.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RulesetError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&contents),
+            Some("json") => Self::from_json(&contents),
+            _ => Err(RulesetError::UnknownFormat),
+        }
+    }
+
+    pub fn game(&self) -> &CyclicGame {
+        &self.game
+    }
+
+    /// Letters of the opponent's column, the i-th one standing for the i-th move.
+    pub fn opponent_letters(&self) -> Vec<char> {
+        letters_in_order(&self.opponent)
+    }
+
+    /// Letters of the player's column, the i-th one standing for the i-th move.
+    pub fn player_letters(&self) -> Vec<char> {
+        letters_in_order(&self.player)
+    }
+
+    pub fn parse_opponent(&self, c: char) -> Result<Move, ParseChoiceError> {
+        self.opponent.get(&c).copied().ok_or(ParseChoiceError)
+    }
+
+    pub fn parse_player(&self, c: char) -> Result<Move, ParseChoiceError> {
+        self.player.get(&c).copied().ok_or(ParseChoiceError)
+    }
+
+    pub fn parse_result(&self, c: char) -> Result<GameResult, ParseGameResultError> {
+        self.results.get(&c).copied().ok_or(ParseGameResultError)
+    }
+
+    pub fn move_score(&self, m: Move) -> u32 {
+        self.move_scores[m.0]
+    }
+
+    pub fn outcome_score(&self, result: &GameResult) -> u32 {
+        match result {
+            GameResult::Win => self.outcome_scores.win,
+            GameResult::Draw => self.outcome_scores.draw,
+            GameResult::Loss => self.outcome_scores.loss,
+        }
+    }
+
+    /// Score of a single round, from the player's point of view.
+    pub fn round_score(&self, theirs: Move, mine: Move) -> u32 {
+        self.move_score(mine) + self.outcome_score(&self.game.pit_against(mine, theirs))
+    }
+}
+
+fn letters_in_order(encoding: &HashMap<char, Move>) -> Vec<char> {
+    let mut letters: Vec<(char, Move)> = encoding.iter().map(|(c, m)| (*c, *m)).collect();
+    letters.sort_by_key(|(_, m)| m.0);
+
+    letters.into_iter().map(|(c, _)| c).collect()
+}
+
+fn encode_moves(
+    letters: &str,
+    column: &'static str,
+    n_moves: usize,
+) -> Result<HashMap<char, Move>, RulesetError> {
+    let found = letters.chars().count();
+
+    if found != n_moves {
+        return Err(RulesetError::WrongNumberOfLetters {
+            column,
+            expected: n_moves,
+            found,
+        });
+    }
+
+    let mut encoding = HashMap::new();
+
+    for (i, c) in letters.chars().enumerate() {
+        if encoding.insert(c, Move(i)).is_some() {
+            return Err(RulesetError::DuplicateLetter(c));
+        }
+    }
+
+    Ok(encoding)
+}
+
+impl TryFrom<RulesetFile> for Ruleset {
+    type Error = RulesetError;
+
+    fn try_from(file: RulesetFile) -> Result<Self, Self::Error> {
+        let n_moves = file.move_scores.len();
+
+        let game = CyclicGame::new(n_moves).map_err(RulesetError::InvalidGame)?;
+
+        let opponent = encode_moves(&file.encodings.opponent, "opponent", n_moves)?;
+        let player = encode_moves(&file.encodings.player, "player", n_moves)?;
+
+        let ResultEncodings { win, draw, loss } = file.encodings.results;
+        let mut results = HashMap::new();
+
+        for (c, result) in [
+            (win, GameResult::Win),
+            (draw, GameResult::Draw),
+            (loss, GameResult::Loss),
+        ] {
+            if results.insert(c, result).is_some() {
+                return Err(RulesetError::DuplicateLetter(c));
+            }
+        }
+
+        Ok(Self {
+            game,
+            move_scores: file.move_scores,
+            outcome_scores: file.outcome_scores,
+            opponent,
+            player,
+            results,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_matches_file() {
+        let ruleset = Ruleset::load("rulesets/default.toml").unwrap();
+        let default = Ruleset::default();
+
+        for c in ['A', 'B', 'C'] {
+            assert_eq!(ruleset.parse_opponent(c), default.parse_opponent(c));
+        }
+
+        for c in ['X', 'Y', 'Z'] {
+            let m = ruleset.parse_player(c).unwrap();
+
+            assert_eq!(Ok(m), default.parse_player(c));
+            assert_eq!(ruleset.move_score(m), default.move_score(m));
+            assert_eq!(
+                ruleset.outcome_score(&ruleset.parse_result(c).unwrap()),
+                default.outcome_score(&default.parse_result(c).unwrap())
+            );
+        }
+
+        assert!(ruleset.parse_opponent('X').is_err());
+    }
+
+    #[test]
+    fn load_json() {
+        let ruleset = Ruleset::load("rulesets/lizard_spock.json").unwrap();
+
+        assert_eq!(ruleset.game().n_moves(), 5);
+        // Spock (W) beats Rock (A), scoring 5 for the move and 10 for the win.
+        assert_eq!(
+            ruleset.round_score(Move(0), ruleset.parse_player('W').unwrap()),
+            15
+        );
+    }
+
+    #[test]
+    fn invalid_rulesets() {
+        let wrong_letters = r#"
+            move_scores = [1, 2, 3]
+            outcome_scores = { win = 6, draw = 3, loss = 0 }
+            encodings = { opponent = "AB", player = "XYZ", results = { loss = "X", draw = "Y", win = "Z" } }
+        "#;
+
+        assert!(matches!(
+            Ruleset::from_toml(wrong_letters),
+            Err(RulesetError::WrongNumberOfLetters {
+                column: "opponent",
+                expected: 3,
+                found: 2
+            })
+        ));
+
+        let even = wrong_letters
+            .replace("[1, 2, 3]", "[1, 2]")
+            .replace("\"XYZ\"", "\"XY\"");
+
+        assert!(matches!(
+            Ruleset::from_toml(&even),
+            Err(RulesetError::InvalidGame(
+                CyclicGameError::EvenNumberOfMoves
+            ))
+        ));
+
+        let duplicate = wrong_letters.replace("\"AB\"", "\"AAB\"");
+
+        assert!(matches!(
+            Ruleset::from_toml(&duplicate),
+            Err(RulesetError::DuplicateLetter('A'))
+        ));
+
+        assert!(matches!(
+            Ruleset::from_json("{}"),
+            Err(RulesetError::Json(_))
+        ));
+        assert!(matches!(
+            Ruleset::with_letters(15),
+            Err(RulesetError::TooManyMoves(15))
+        ));
+    }
+}
@@ -0,0 +1,346 @@
+use std::cmp::{Ordering, Reverse};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    cyclic::{CyclicGame, Move},
+    game::GameResult,
+    guide::{parse_file_contents, ParseError},
+    ruleset::Ruleset,
+};
+
+/// A strategy that picks a move every round, possibly learning from the opponent.
+pub trait Player {
+    fn name(&self) -> &str;
+
+    /// Picks one of the `game.n_moves()` moves of the game being played.
+    fn choose(&mut self, game: &CyclicGame) -> Move;
+
+    /// Called after every round with both moves that were played.
+    fn observe(&mut self, _mine: Move, _theirs: Move) {}
+
+    /// Called before every match, so that nothing is carried over between opponents.
+    fn reset(&mut self) {}
+}
+
+fn beat(game: &CyclicGame, theirs: Move) -> Move {
+    game.get_move_to_ensure_result_against(theirs, &GameResult::Win)
+}
+
+/// Plays the given moves in order, starting over once they run out. Plays the first
+/// move of the game if there are no moves at all.
+pub struct FixedSequence {
+    name: String,
+    moves: Vec<Move>,
+    position: usize,
+}
+
+impl FixedSequence {
+    pub fn new(name: &str, moves: Vec<Move>) -> Self {
+        Self {
+            name: name.to_string(),
+            moves,
+            position: 0,
+        }
+    }
+
+    /// Plays the second column of a strategy guide, read as moves of the ruleset.
+    pub fn from_guide(name: &str, contents: &str, ruleset: &Ruleset) -> Result<Self, ParseError> {
+        let guide = parse_file_contents(
+            contents,
+            |c| ruleset.parse_opponent(c),
+            |c| ruleset.parse_player(c),
+        )?;
+
+        Ok(Self::new(
+            name,
+            guide.into_iter().map(|(_, mine)| mine).collect(),
+        ))
+    }
+}
+
+impl Player for FixedSequence {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn choose(&mut self, game: &CyclicGame) -> Move {
+        let m = self
+            .moves
+            .get(self.position % self.moves.len().max(1))
+            .copied()
+            .filter(|m| m.0 < game.n_moves())
+            .unwrap_or(Move(0));
+        self.position += 1;
+
+        m
+    }
+
+    fn reset(&mut self) {
+        self.position = 0;
+    }
+}
+
+/// Picks each move with equal probability. Seeded so that tournaments can be
+/// replayed.
+pub struct Random {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Player for Random {
+    fn name(&self) -> &str {
+        "Random"
+    }
+
+    fn choose(&mut self, game: &CyclicGame) -> Move {
+        Move(self.rng.gen_range(0..game.n_moves()))
+    }
+
+    fn reset(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+    }
+}
+
+/// Plays whatever beats the opponent's most frequent move so far.
+#[derive(Default)]
+pub struct FrequencyCounter {
+    /// Number of times the opponent played each move, indexed by move.
+    counts: Vec<u32>,
+}
+
+impl Player for FrequencyCounter {
+    fn name(&self) -> &str {
+        "Frequency counter"
+    }
+
+    fn choose(&mut self, game: &CyclicGame) -> Move {
+        let most_frequent = (0..game.n_moves())
+            .max_by_key(|&i| (self.counts.get(i).copied().unwrap_or(0), Reverse(i)))
+            .unwrap_or(0);
+
+        beat(game, Move(most_frequent))
+    }
+
+    fn observe(&mut self, _mine: Move, theirs: Move) {
+        if self.counts.len() <= theirs.0 {
+            self.counts.resize(theirs.0 + 1, 0);
+        }
+        self.counts[theirs.0] += 1;
+    }
+
+    fn reset(&mut self) {
+        self.counts.clear();
+    }
+}
+
+/// Plays whatever beats the opponent's previous move, starting with the first move
+/// of the game.
+#[derive(Default)]
+pub struct BeatLastMove {
+    last: Option<Move>,
+}
+
+impl Player for BeatLastMove {
+    fn name(&self) -> &str {
+        "Beat last move"
+    }
+
+    fn choose(&mut self, game: &CyclicGame) -> Move {
+        self.last.map_or(Move(0), |theirs| beat(game, theirs))
+    }
+
+    fn observe(&mut self, _mine: Move, theirs: Move) {
+        self.last = Some(theirs);
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Standing {
+    pub name: String,
+    /// Sum of the round scores over all matches, as scored by the ruleset.
+    pub score: u64,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+/// Plays a match of `rounds` rounds between two players under the ruleset, returning
+/// their scores.
+pub fn play_match(
+    first: &mut dyn Player,
+    second: &mut dyn Player,
+    rounds: usize,
+    ruleset: &Ruleset,
+) -> (u64, u64) {
+    first.reset();
+    second.reset();
+
+    let game = ruleset.game();
+    let (mut first_score, mut second_score) = (0, 0);
+
+    for _ in 0..rounds {
+        let (a, b) = (first.choose(game), second.choose(game));
+
+        first_score += u64::from(ruleset.round_score(b, a));
+        second_score += u64::from(ruleset.round_score(a, b));
+
+        first.observe(a, b);
+        second.observe(b, a);
+    }
+
+    (first_score, second_score)
+}
+
+/// Makes every player face every other one once, returning the standings sorted by
+/// match wins and then by total score.
+pub fn round_robin(
+    players: &mut [Box<dyn Player>],
+    rounds: usize,
+    ruleset: &Ruleset,
+) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = players
+        .iter()
+        .map(|player| Standing {
+            name: player.name().to_string(),
+            score: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        })
+        .collect();
+
+    for i in 0..players.len() {
+        for j in (i + 1)..players.len() {
+            let (left, right) = players.split_at_mut(j);
+            let (first_score, second_score) =
+                play_match(left[i].as_mut(), right[0].as_mut(), rounds, ruleset);
+
+            standings[i].score += first_score;
+            standings[j].score += second_score;
+
+            match first_score.cmp(&second_score) {
+                Ordering::Greater => {
+                    standings[i].wins += 1;
+                    standings[j].losses += 1;
+                }
+                Ordering::Less => {
+                    standings[i].losses += 1;
+                    standings[j].wins += 1;
+                }
+                Ordering::Equal => {
+                    standings[i].draws += 1;
+                    standings[j].draws += 1;
+                }
+            }
+        }
+    }
+
+    standings.sort_by_key(|standing| Reverse((standing.wins, standing.score)));
+
+    standings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CONTENT: &str = include_str!("../test_input.txt");
+
+    const ROCK: Move = Move(0);
+    const PAPER: Move = Move(1);
+    const SCISSORS: Move = Move(2);
+
+    #[test]
+    fn guide_player() {
+        let ruleset = Ruleset::default();
+        let mut guide = FixedSequence::from_guide("Guide", TEST_CONTENT, &ruleset).unwrap();
+        let mut opponent = FixedSequence::new("Opponent", vec![ROCK, PAPER, SCISSORS]);
+
+        // Same as scoring the guide in part one.
+        assert_eq!(play_match(&mut guide, &mut opponent, 3, &ruleset).0, 15);
+        // Starts over after a reset, and cycles through the sequence.
+        assert_eq!(play_match(&mut guide, &mut opponent, 6, &ruleset).0, 30);
+    }
+
+    #[test]
+    fn adaptive_players() {
+        let ruleset = Ruleset::default();
+        let mut rock = FixedSequence::new("Rock", vec![ROCK]);
+        let mut counter = FrequencyCounter::default();
+        let mut beat_last = BeatLastMove::default();
+
+        // With no history, Rock is taken as the most frequent move.
+        assert_eq!(
+            play_match(&mut counter, &mut rock, 10, &ruleset),
+            (10 * 8, 10)
+        );
+        // Draws the first round, then keeps playing Paper.
+        assert_eq!(
+            play_match(&mut beat_last, &mut rock, 10, &ruleset),
+            (4 + 9 * 8, 4 + 9)
+        );
+    }
+
+    #[test]
+    fn larger_games() {
+        let ruleset = Ruleset::with_letters(5).unwrap();
+        let game = ruleset.game();
+        let mut counter = FrequencyCounter::default();
+
+        counter.observe(ROCK, Move(4));
+        counter.observe(ROCK, Move(4));
+        counter.observe(ROCK, Move(3));
+        assert_eq!(counter.choose(game), Move(0));
+
+        let mut random = Random::new(7);
+        assert!((0..100).all(|_| random.choose(game).0 < 5));
+    }
+
+    #[test]
+    fn random_player_is_reproducible() {
+        let game = CyclicGame::rock_paper_scissors();
+        let mut random = Random::new(42);
+
+        let first: Vec<Move> = (0..20).map(|_| random.choose(&game)).collect();
+        random.reset();
+        let second: Vec<Move> = (0..20).map(|_| random.choose(&game)).collect();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn leaderboard() {
+        let mut players: Vec<Box<dyn Player>> = vec![
+            Box::new(FixedSequence::new("Rock", vec![ROCK])),
+            Box::new(BeatLastMove::default()),
+            Box::new(FixedSequence::new("Scissors", vec![SCISSORS])),
+        ];
+
+        let standings = round_robin(&mut players, 10, &Ruleset::default());
+
+        assert_eq!(
+            standings
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Beat last move", "Rock", "Scissors"]
+        );
+        assert_eq!(standings[0].wins, 2);
+        assert_eq!(standings[1].wins, 1);
+        assert_eq!(standings[2].losses, 2);
+    }
+}
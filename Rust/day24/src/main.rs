@@ -0,0 +1,258 @@
+use std::collections::{HashSet, VecDeque};
+
+use ndarray::Array2;
+use thiserror::Error;
+
+const INPUT: &str = include_str!("../input.txt");
+
+#[derive(Debug, Error)]
+#[error("Invalid tile")]
+struct TileError {
+    wrong_input: char,
+}
+
+#[derive(Debug, Error)]
+#[error("Valley parsing error.")]
+enum ValleyParsingError {
+    TileError(#[from] TileError),
+    MalformedWalls,
+}
+
+#[derive(Debug, Error)]
+#[error("No path through the valley")]
+struct NoPath;
+
+#[derive(Debug, Error)]
+#[error("Execution error")]
+enum ExecutionError {
+    ValleyParsingError(#[from] ValleyParsingError),
+    NoPath(#[from] NoPath),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum Tile {
+    #[default]
+    Clear,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = TileError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Tile::Clear),
+            '^' => Ok(Tile::Up),
+            'v' => Ok(Tile::Down),
+            '<' => Ok(Tile::Left),
+            '>' => Ok(Tile::Right),
+            x => Err(TileError { wrong_input: x }),
+        }
+    }
+}
+
+/// Position in the valley, given as (row, column) of the full map, walls included.
+type Position = (usize, usize);
+
+/// The valley with its walls stripped away. Only the initial blizzard layout is
+/// stored: since every blizzard wraps around its own row or column, the layout at
+/// any given time can be recovered analytically from the initial one.
+struct Valley {
+    blizzards: Array2<Tile>,
+    start: Position,
+    end: Position,
+    period: usize,
+}
+
+impl Valley {
+    fn height(&self) -> usize {
+        self.blizzards.nrows()
+    }
+
+    fn width(&self) -> usize {
+        self.blizzards.ncols()
+    }
+
+    /// Checks whether a blizzard sits on the given inner cell at time `t`, by looking
+    /// at the four cells whose blizzards could have reached it.
+    fn has_blizzard(&self, (i, j): (usize, usize), t: usize) -> bool {
+        let (h, w) = (self.height(), self.width());
+
+        self.blizzards[((i + t) % h, j)] == Tile::Up
+            || self.blizzards[((i + h - t % h) % h, j)] == Tile::Down
+            || self.blizzards[(i, (j + t) % w)] == Tile::Left
+            || self.blizzards[(i, (j + w - t % w) % w)] == Tile::Right
+    }
+
+    fn is_free(&self, pos: Position, t: usize) -> bool {
+        if pos == self.start || pos == self.end {
+            return true;
+        }
+
+        match pos {
+            (i, j) if (1..=self.height()).contains(&i) && (1..=self.width()).contains(&j) => {
+                !self.has_blizzard((i - 1, j - 1), t)
+            }
+            _ => false,
+        }
+    }
+
+    fn candidates(&self, pos: Position) -> Vec<Position> {
+        let mut candidates = vec![pos, (pos.0 + 1, pos.1), (pos.0, pos.1 + 1)];
+
+        if pos.0 > 0 {
+            candidates.push((pos.0 - 1, pos.1));
+        }
+
+        if pos.1 > 0 {
+            candidates.push((pos.0, pos.1 - 1));
+        }
+
+        candidates
+    }
+
+    /// Breadth-first search over (position, time) states, starting at time
+    /// `departure`. Returns the time of arrival at `to`.
+    ///
+    /// Since the blizzard layout repeats every `period` steps, states are
+    /// deduplicated on the time modulo the period.
+    fn fastest_trip(
+        &self,
+        from: Position,
+        to: Position,
+        departure: usize,
+    ) -> Result<usize, NoPath> {
+        let mut visited = HashSet::from([(from, departure % self.period)]);
+        let mut queue = VecDeque::from([(from, departure)]);
+
+        while let Some((pos, t)) = queue.pop_front() {
+            if pos == to {
+                return Ok(t);
+            }
+
+            for candidate in self.candidates(pos) {
+                if self.is_free(candidate, t + 1)
+                    && visited.insert((candidate, (t + 1) % self.period))
+                {
+                    queue.push_back((candidate, t + 1));
+                }
+            }
+        }
+
+        Err(NoPath)
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+fn get_valley(contents: &str) -> Result<Valley, ValleyParsingError> {
+    let lines: Vec<&str> = contents.lines().collect();
+
+    let (first, last) = match lines[..] {
+        [first, .., last] if lines.len() > 2 => (first, last),
+        _ => return Err(ValleyParsingError::MalformedWalls),
+    };
+
+    let n_rows = lines.len() - 2;
+    let n_columns = first
+        .len()
+        .checked_sub(2)
+        .ok_or(ValleyParsingError::MalformedWalls)?;
+
+    let start_col = first.find('.').ok_or(ValleyParsingError::MalformedWalls)?;
+    let end_col = last.find('.').ok_or(ValleyParsingError::MalformedWalls)?;
+
+    let mut blizzards: Array2<Tile> = Array2::default((n_rows, n_columns));
+
+    for (i, line) in lines[1..=n_rows].iter().enumerate() {
+        let inner = line
+            .strip_prefix('#')
+            .and_then(|line| line.strip_suffix('#'))
+            .filter(|inner| inner.len() == n_columns)
+            .ok_or(ValleyParsingError::MalformedWalls)?;
+
+        for (j, c) in inner.chars().enumerate() {
+            blizzards[(i, j)] = Tile::try_from(c)?;
+        }
+    }
+
+    Ok(Valley {
+        blizzards,
+        start: (0, start_col),
+        end: (n_rows + 1, end_col),
+        period: lcm(n_rows, n_columns),
+    })
+}
+
+fn solve_part_one(contents: &str) -> Result<usize, ExecutionError> {
+    let valley = get_valley(contents)?;
+
+    Ok(valley.fastest_trip(valley.start, valley.end, 0)?)
+}
+
+fn solve_part_two(contents: &str) -> Result<usize, ExecutionError> {
+    let valley = get_valley(contents)?;
+
+    let there = valley.fastest_trip(valley.start, valley.end, 0)?;
+    let back = valley.fastest_trip(valley.end, valley.start, there)?;
+
+    Ok(valley.fastest_trip(valley.start, valley.end, back)?)
+}
+
+fn main() -> Result<(), ExecutionError> {
+    println!("{}", solve_part_one(INPUT)?);
+    println!("{}", solve_part_two(INPUT)?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const TEST: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn blizzards_wrap_around() {
+        let valley = get_valley(TEST).unwrap();
+
+        // The '>' at the top-left of the valley moves right one cell per minute.
+        assert!(valley.has_blizzard((0, 0), 0));
+        assert!(valley.has_blizzard((0, 1), 1));
+        assert_eq!(valley.period, 12);
+
+        // The layout repeats once a full period has passed.
+        for t in 0..valley.period {
+            for i in 0..valley.height() {
+                for j in 0..valley.width() {
+                    assert_eq!(
+                        valley.has_blizzard((i, j), t),
+                        valley.has_blizzard((i, j), t + valley.period)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn part_one() {
+        assert_eq!(solve_part_one(TEST).unwrap(), 18)
+    }
+
+    #[test]
+    fn part_two() {
+        assert_eq!(solve_part_two(TEST).unwrap(), 54)
+    }
+}
@@ -0,0 +1,38 @@
+use day25::snafu::{ParseSnafuError, Snafu};
+
+const INPUT: &str = include_str!("../input.txt");
+
+/// Sums the numerals, failing if the total is out of range instead of overflowing.
+fn solve(contents: &str) -> Result<Snafu, ParseSnafuError> {
+    contents.lines().try_fold(Snafu::default(), |acc, line| {
+        acc.checked_add(line.parse()?)
+            .ok_or(ParseSnafuError::Overflow)
+    })
+}
+
+fn main() -> Result<(), ParseSnafuError> {
+    println!("{}", solve(INPUT)?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const TEST: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn test_solve() {
+        let sum = solve(TEST).unwrap();
+
+        assert_eq!(i64::try_from(sum).unwrap(), 4890);
+        assert_eq!(sum.to_string(), "2=-1=0");
+    }
+
+    #[test]
+    fn test_overflow() {
+        let contents = vec!["2".repeat(54); 20].join("\n");
+
+        assert_eq!(solve(&contents), Err(ParseSnafuError::Overflow));
+    }
+}
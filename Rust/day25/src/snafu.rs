@@ -0,0 +1,282 @@
+use std::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, Neg},
+    str::FromStr,
+};
+
+use thiserror::Error;
+
+/// A number written in SNAFU notation: base 5, but with digits ranging from -2 to 2,
+/// written as `=`, `-`, `0`, `1` and `2`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Snafu(i128);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseSnafuError {
+    #[error("Empty SNAFU numeral")]
+    Empty,
+
+    #[error("Invalid SNAFU digit: {0}")]
+    InvalidDigit(char),
+
+    #[error("SNAFU numeral out of range")]
+    Overflow,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("SNAFU number out of range")]
+pub struct SnafuRangeError;
+
+fn digit_value(c: char) -> Result<i128, ParseSnafuError> {
+    match c {
+        '=' => Ok(-2),
+        '-' => Ok(-1),
+        '0' => Ok(0),
+        '1' => Ok(1),
+        '2' => Ok(2),
+        _ => Err(ParseSnafuError::InvalidDigit(c)),
+    }
+}
+
+fn digit_char(value: i128) -> char {
+    match value {
+        -2 => '=',
+        -1 => '-',
+        0 => '0',
+        1 => '1',
+        2 => '2',
+        _ => unreachable!(),
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseSnafuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseSnafuError::Empty);
+        }
+
+        s.chars()
+            .try_fold(0i128, |acc, c| {
+                let digit = digit_value(c)?;
+
+                // Computed as 4 * acc + (acc + digit) so that numbers whose negative
+                // last digit brings them back into range don't overflow midway.
+                acc.checked_mul(4)
+                    .zip(acc.checked_add(digit))
+                    .and_then(|(a, b)| a.checked_add(b))
+                    .ok_or(ParseSnafuError::Overflow)
+            })
+            .map(Snafu)
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "0");
+        }
+
+        let mut digits = Vec::new();
+        let mut value = self.0;
+
+        while value != 0 {
+            // Remainders 3 and 4 become -2 and -1, carrying one into the next digit.
+            let (quotient, remainder) = (value.div_euclid(5), value.rem_euclid(5));
+
+            if remainder > 2 {
+                digits.push(digit_char(remainder - 5));
+                value = quotient + 1;
+            } else {
+                digits.push(digit_char(remainder));
+                value = quotient;
+            }
+        }
+
+        write!(f, "{}", digits.iter().rev().collect::<String>())
+    }
+}
+
+impl Snafu {
+    /// Adds two numbers, returning `None` if the result is out of range.
+    pub fn checked_add(self, rhs: Self) -> Option<Snafu> {
+        self.0.checked_add(rhs.0).map(Snafu)
+    }
+
+    /// Negates the number, returning `None` if the result is out of range, which
+    /// only happens for the smallest one.
+    pub fn checked_neg(self) -> Option<Snafu> {
+        self.0.checked_neg().map(Snafu)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    /// Panics if the result is out of range, use [`Snafu::checked_add`] to handle it
+    /// instead.
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("SNAFU addition overflowed")
+    }
+}
+
+impl Neg for Snafu {
+    type Output = Snafu;
+
+    /// Panics if the result is out of range, use [`Snafu::checked_neg`] to handle it
+    /// instead.
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("SNAFU negation overflowed")
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        Snafu(value.into())
+    }
+}
+
+impl From<i128> for Snafu {
+    fn from(value: i128) -> Self {
+        Snafu(value)
+    }
+}
+
+impl From<Snafu> for i128 {
+    fn from(snafu: Snafu) -> Self {
+        snafu.0
+    }
+}
+
+impl TryFrom<Snafu> for i64 {
+    type Error = SnafuRangeError;
+
+    fn try_from(snafu: Snafu) -> Result<Self, Self::Error> {
+        snafu.0.try_into().map_err(|_| SnafuRangeError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn decimal_examples() {
+        let examples = [
+            (1i64, "1"),
+            (2, "2"),
+            (3, "1="),
+            (4, "1-"),
+            (5, "10"),
+            (8, "2="),
+            (10, "20"),
+            (15, "1=0"),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+        ];
+
+        for (decimal, snafu) in examples {
+            assert_eq!(Snafu::from(decimal).to_string(), snafu);
+            assert_eq!(i64::try_from(snafu.parse::<Snafu>().unwrap()), Ok(decimal));
+        }
+    }
+
+    #[test]
+    fn invalid_numerals() {
+        assert_eq!("".parse::<Snafu>(), Err(ParseSnafuError::Empty));
+        assert_eq!(
+            "1=3".parse::<Snafu>(),
+            Err(ParseSnafuError::InvalidDigit('3'))
+        );
+        assert_eq!(
+            "2".repeat(60).parse::<Snafu>(),
+            Err(ParseSnafuError::Overflow)
+        );
+    }
+
+    #[test]
+    fn out_of_range_conversion() {
+        let snafu = Snafu::from(i64::MAX) + Snafu::from(1i64);
+
+        assert_eq!(i64::try_from(snafu), Err(SnafuRangeError));
+        assert_eq!(i128::from(snafu), i64::MAX as i128 + 1);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let large: Snafu = "2".repeat(54).parse().unwrap();
+
+        assert_eq!(
+            (1..20).try_fold(large, |acc, _| acc.checked_add(large)),
+            None
+        );
+        assert_eq!(
+            Snafu::from(2i64).checked_add(Snafu::from(-5i64)),
+            Some(Snafu::from(-3i64))
+        );
+
+        assert_eq!(Snafu::from(i128::MIN).checked_neg(), None);
+        assert_eq!(
+            Snafu::from(i128::MAX).checked_neg(),
+            Some(Snafu::from(-i128::MAX))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "SNAFU negation overflowed")]
+    fn negation_overflow() {
+        let _ = -Snafu::from(i128::MIN);
+    }
+
+    #[quickcheck]
+    fn round_trip_i64(value: i64) -> bool {
+        i64::try_from(Snafu::from(value)) == Ok(value)
+    }
+
+    #[quickcheck]
+    fn round_trip_string(value: i64) -> bool {
+        let snafu = Snafu::from(value);
+
+        snafu.to_string().parse::<Snafu>() == Ok(snafu)
+    }
+
+    #[quickcheck]
+    fn round_trip_i128_string(value: i128) -> bool {
+        let snafu = Snafu::from(value);
+
+        snafu.to_string().parse::<Snafu>() == Ok(snafu) && i128::from(snafu) == value
+    }
+
+    #[test]
+    fn round_trip_extremes() {
+        for value in [i128::MIN, i128::MAX, i64::MIN as i128, i64::MAX as i128] {
+            let snafu = Snafu::from(value);
+
+            assert_eq!(snafu.to_string().parse::<Snafu>(), Ok(snafu));
+        }
+    }
+
+    #[quickcheck]
+    fn addition_matches_integers(a: i32, b: i32) -> bool {
+        let sum: Snafu = [Snafu::from(a as i64), Snafu::from(b as i64)].iter().sum();
+
+        i64::try_from(sum) == Ok(a as i64 + b as i64)
+    }
+}
@@ -0,0 +1,130 @@
+use thiserror::Error;
+
+use crate::{
+    items::{NoPriority, Priority},
+    rucksack::{get_common_items_across_rucksacks, Rucksack},
+};
+
+/// The single item shared by every rucksack of a group.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Badge {
+    pub item: char,
+    pub priority: u32,
+    /// Lines of the group's rucksacks, numbered from 1.
+    pub lines: Vec<usize>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BadgeError {
+    #[error("Group size must be positive")]
+    EmptyGroups,
+
+    #[error("{n_rucksacks} rucksacks can't be split in groups of {group_size}")]
+    IncompleteGroup {
+        n_rucksacks: usize,
+        group_size: usize,
+    },
+
+    #[error("No item in common for the group at lines {lines:?}")]
+    NoBadge { lines: Vec<usize> },
+
+    #[error("Several items ({items}) in common for the group at lines {lines:?}")]
+    SeveralBadges { lines: Vec<usize>, items: String },
+
+    #[error("Item without priority")]
+    NoPriority(#[from] NoPriority),
+}
+
+/// Splits the rucksacks in groups of `group_size` and finds the badge of each one,
+/// erroring unless every group shares exactly one item.
+pub fn find_badges(rucksacks: &[Rucksack], group_size: usize) -> Result<Vec<Badge>, BadgeError> {
+    if group_size == 0 {
+        return Err(BadgeError::EmptyGroups);
+    }
+
+    if !rucksacks.len().is_multiple_of(group_size) {
+        return Err(BadgeError::IncompleteGroup {
+            n_rucksacks: rucksacks.len(),
+            group_size,
+        });
+    }
+
+    rucksacks
+        .chunks(group_size)
+        .map(|group| {
+            let lines: Vec<usize> = group.iter().map(Rucksack::line).collect();
+
+            let common = get_common_items_across_rucksacks(group)?;
+            let mut items = common.iter();
+
+            match (items.next(), items.next()) {
+                (Some(item), None) => Ok(Badge {
+                    item,
+                    priority: item.priority()?,
+                    lines,
+                }),
+                (None, _) => Err(BadgeError::NoBadge { lines }),
+                (Some(_), Some(_)) => Err(BadgeError::SeveralBadges {
+                    lines,
+                    items: common.iter().collect(),
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rucksack::get_rucksacks;
+
+    const TEST_INPUT: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn badges() {
+        let rucksacks = get_rucksacks(TEST_INPUT).unwrap();
+        let badges = find_badges(&rucksacks, 3).unwrap();
+
+        assert_eq!(
+            badges,
+            vec![
+                Badge {
+                    item: 'r',
+                    priority: 18,
+                    lines: vec![1, 2, 3]
+                },
+                Badge {
+                    item: 'Z',
+                    priority: 52,
+                    lines: vec![4, 5, 6]
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn group_errors() {
+        let rucksacks = get_rucksacks(TEST_INPUT).unwrap();
+
+        assert_eq!(
+            find_badges(&rucksacks, 4),
+            Err(BadgeError::IncompleteGroup {
+                n_rucksacks: 6,
+                group_size: 4
+            })
+        );
+        assert_eq!(find_badges(&rucksacks, 0), Err(BadgeError::EmptyGroups));
+
+        assert_eq!(
+            find_badges(&get_rucksacks("ab\ncd").unwrap(), 2),
+            Err(BadgeError::NoBadge { lines: vec![1, 2] })
+        );
+        assert_eq!(
+            find_badges(&get_rucksacks("abcb\nbcaa\nxcbx").unwrap(), 3),
+            Err(BadgeError::SeveralBadges {
+                lines: vec![1, 2, 3],
+                items: "bc".to_string()
+            })
+        );
+    }
+}
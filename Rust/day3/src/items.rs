@@ -0,0 +1,108 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Item without priority")]
+pub struct NoPriority;
+
+pub trait Priority {
+    fn priority(&self) -> Result<u32, NoPriority>;
+}
+
+impl Priority for char {
+    fn priority(&self) -> Result<u32, NoPriority> {
+        match self {
+            'a'..='z' => Ok(*self as u32 - 'a' as u32 + 1),
+            'A'..='Z' => Ok(*self as u32 - 'A' as u32 + 27),
+            _ => Err(NoPriority),
+        }
+    }
+}
+
+/// Set of items, stored as a bitmask where bit `n` is set if the item with
+/// priority `n` is present.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn insert(&mut self, item: char) -> Result<(), NoPriority> {
+        self.0 |= 1 << item.priority()?;
+
+        Ok(())
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        item.priority()
+            .map(|priority| self.0 & (1 << priority) != 0)
+            .unwrap_or(false)
+    }
+
+    pub fn intersection(&self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(&self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Priorities of the items in the set, in increasing order.
+    pub fn priorities(&self) -> impl Iterator<Item = u32> {
+        let bits = self.0;
+
+        (1..=52).filter(move |priority| bits & (1 << priority) != 0)
+    }
+
+    /// Items in the set, sorted by priority.
+    pub fn iter(&self) -> impl Iterator<Item = char> {
+        self.priorities().map(|priority| match priority {
+            1..=26 => (b'a' + priority as u8 - 1) as char,
+            _ => (b'A' + priority as u8 - 27) as char,
+        })
+    }
+}
+
+impl TryFrom<&str> for ItemSet {
+    type Error = NoPriority;
+
+    fn try_from(items: &str) -> Result<Self, Self::Error> {
+        let mut set = ItemSet::default();
+
+        for item in items.chars() {
+            set.insert(item)?;
+        }
+
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_set() {
+        let first = ItemSet::try_from("vJrwpWtwJgWr").unwrap();
+        let second = ItemSet::try_from("hcsFMMfFFhFp").unwrap();
+
+        assert_eq!(first.intersection(second).iter().collect::<String>(), "p");
+        assert_eq!(first.len(), 8);
+        assert_eq!(first.union(second).len(), 8 + 7 - 1);
+        assert!(first.contains('J'));
+        assert!(!first.contains('F'));
+        assert!(!first.contains('!'));
+
+        let all = ItemSet::try_from("zaZA").unwrap();
+        assert_eq!(all.iter().collect::<String>(), "azAZ");
+        assert_eq!(all.priorities().collect::<Vec<_>>(), [1, 26, 27, 52]);
+
+        assert!(ItemSet::try_from("ab1").is_err());
+        assert!(ItemSet::default().is_empty());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod badge;
+pub mod items;
+pub mod rucksack;
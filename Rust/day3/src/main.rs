@@ -1,92 +1,80 @@
-use std::collections::HashSet;
+use day3::{
+    badge::{find_badges, BadgeError},
+    items::{NoPriority, Priority},
+    rucksack::{get_rucksacks, RucksackError},
+};
+use thiserror::Error;
 
 const INPUT: &str = include_str!("../input.txt");
+const GROUP_SIZE: usize = 3;
 
-struct Rucksack<'a> {
-    items: &'a str,
-}
-
-impl Rucksack<'_> {
-    fn get_compartments(&self) -> (&str, &str) {
-        let (first, second) = self.items.split_at(self.items.len() / 2);
-
-        (first, second)
-    }
-
-    fn get_common_items(&self) -> HashSet<char> {
-        let (first, second) = self.get_compartments();
-
-        first.chars().filter(|c| second.contains(*c)).collect()
-    }
-}
+/// Sums the priorities of the items shared by both compartments. In strict mode,
+/// every rucksack must share exactly one item.
+fn solve_part_one(contents: &str, strict: bool) -> Result<u32, ExecutionError> {
+    let rucksacks = get_rucksacks(contents)?;
 
-fn get_common_items_across_rucksacks(rs: &[Rucksack]) -> HashSet<char> {
-    if let Some(first) = rs.first() {
-        first
-            .items
-            .chars()
-            .filter(|c| rs.iter().skip(1).all(|r| r.items.contains(*c)))
-            .collect()
-    } else {
-        HashSet::new()
-    }
+    rucksacks
+        .iter()
+        .map(|rs| {
+            if strict {
+                Ok(rs.get_shared_item()?.priority()?)
+            } else {
+                Ok(rs.get_common_items()?.priorities().sum::<u32>())
+            }
+        })
+        .sum()
 }
 
-#[derive(Debug)]
-struct NoPriority;
+fn solve_part_two(contents: &str, group_size: usize) -> Result<u32, ExecutionError> {
+    let rucksacks = get_rucksacks(contents)?;
 
-trait Priority {
-    fn priority(&self) -> Result<u32, NoPriority>;
+    Ok(find_badges(&rucksacks, group_size)?
+        .iter()
+        .map(|badge| badge.priority)
+        .sum())
 }
 
-impl Priority for char {
-    fn priority(&self) -> Result<u32, NoPriority> {
-        match self {
-            'a'..='z' => Ok(*self as u32 - 'a' as u32 + 1),
-            'A'..='Z' => Ok(*self as u32 - 'A' as u32 + 27),
-            _ => Err(NoPriority),
-        }
+fn print_badges(contents: &str, group_size: usize) -> Result<(), ExecutionError> {
+    let rucksacks = get_rucksacks(contents)?;
+
+    for badge in find_badges(&rucksacks, group_size)? {
+        println!(
+            "Lines {:?}: {} (priority {})",
+            badge.lines, badge.item, badge.priority
+        );
     }
-}
 
-fn get_rucksacks(contents: &str) -> Vec<Rucksack> {
-    contents
-        .lines()
-        .map(|line| Rucksack { items: line })
-        .collect()
+    Ok(())
 }
 
-fn solve_part_one(contents: &str) -> Result<u32, NoPriority> {
-    let rucksacks = get_rucksacks(contents);
+#[derive(Error, Debug)]
+enum ExecutionError {
+    #[error("Priority error")]
+    NoPriority(#[from] NoPriority),
 
-    rucksacks
-        .iter()
-        .map(|rs| {
-            rs.get_common_items()
-                .iter()
-                .map(|c| c.priority())
-                .sum::<Result<u32, NoPriority>>()
-        })
-        .sum()
-}
+    #[error("Rucksack error")]
+    RucksackError(#[from] RucksackError),
 
-fn solve_part_two(contents: &str) -> Result<u32, NoPriority> {
-    let rucksacks = get_rucksacks(contents);
+    #[error("Badge error")]
+    BadgeError(#[from] BadgeError),
 
-    rucksacks
-        .chunks(3)
-        .map(|rs| {
-            get_common_items_across_rucksacks(rs)
-                .iter()
-                .map(|c| c.priority())
-                .sum::<Result<u32, NoPriority>>()
-        })
-        .sum()
+    #[error("Invalid group size")]
+    InvalidGroupSize(#[from] std::num::ParseIntError),
 }
 
-fn main() -> Result<(), NoPriority> {
-    println!("{}", solve_part_one(INPUT)?);
-    println!("{}", solve_part_two(INPUT)?);
+fn main() -> Result<(), ExecutionError> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args[..] {
+        ["strict"] => println!("{}", solve_part_one(INPUT, true)?),
+        ["badges"] => print_badges(INPUT, GROUP_SIZE)?,
+        ["badges", group_size] => print_badges(INPUT, group_size.parse()?)?,
+        _ => {
+            println!("{}", solve_part_one(INPUT, false)?);
+            println!("{}", solve_part_two(INPUT, GROUP_SIZE)?);
+        }
+    }
 
     Ok(())
 }
@@ -98,11 +86,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        assert_eq!(solve_part_one(TEST_INPUT).unwrap(), 157);
+        assert_eq!(solve_part_one(TEST_INPUT, false).unwrap(), 157);
+        assert_eq!(solve_part_one(TEST_INPUT, true).unwrap(), 157);
+        assert!(solve_part_one("abab", false).is_ok());
+        assert!(solve_part_one("abab", true).is_err());
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(solve_part_two(TEST_INPUT).unwrap(), 70);
+        assert_eq!(solve_part_two(TEST_INPUT, 3).unwrap(), 70);
+        assert!(solve_part_two(TEST_INPUT, 4).is_err());
     }
 }
@@ -0,0 +1,162 @@
+use thiserror::Error;
+
+use crate::items::{ItemSet, NoPriority, Priority};
+
+pub struct Rucksack<'a> {
+    items: &'a str,
+    /// Line of the rucksack in the input, numbered from 1.
+    line: usize,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RucksackError {
+    #[error("Odd number of items ({length}) at line {line}")]
+    OddLength { line: usize, length: usize },
+
+    #[error("Invalid item {item:?} at line {line}, column {column}")]
+    InvalidItem {
+        line: usize,
+        column: usize,
+        item: char,
+    },
+
+    #[error("No item shared by both compartments at line {line}")]
+    NoSharedItem { line: usize },
+
+    #[error("Several items ({items}) shared by both compartments at line {line}")]
+    SeveralSharedItems { line: usize, items: String },
+}
+
+impl<'a> Rucksack<'a> {
+    /// Builds a rucksack, checking that it can be split in two compartments of the
+    /// same size and that every item has a priority.
+    pub fn new(items: &'a str, line: usize) -> Result<Self, RucksackError> {
+        if let Some((i, item)) = items
+            .chars()
+            .enumerate()
+            .find(|(_, c)| c.priority().is_err())
+        {
+            return Err(RucksackError::InvalidItem {
+                line,
+                column: i + 1,
+                item,
+            });
+        }
+
+        if !items.len().is_multiple_of(2) {
+            return Err(RucksackError::OddLength {
+                line,
+                length: items.len(),
+            });
+        }
+
+        Ok(Self { items, line })
+    }
+}
+
+impl<'a> Rucksack<'a> {
+    pub fn items(&self) -> &'a str {
+        self.items
+    }
+
+    /// Line of the rucksack in the input, numbered from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn get_compartments(&self) -> (&str, &str) {
+        let (first, second) = self.items.split_at(self.items.len() / 2);
+
+        (first, second)
+    }
+
+    pub fn get_common_items(&self) -> Result<ItemSet, NoPriority> {
+        let (first, second) = self.get_compartments();
+
+        Ok(ItemSet::try_from(first)?.intersection(ItemSet::try_from(second)?))
+    }
+
+    /// Gets the single item shared by both compartments, erroring if there are none
+    /// or several.
+    pub fn get_shared_item(&self) -> Result<char, RucksackError> {
+        let common = self
+            .get_common_items()
+            .expect("Items are validated when building the rucksack");
+        let mut items = common.iter();
+
+        match (items.next(), items.next()) {
+            (Some(item), None) => Ok(item),
+            (None, _) => Err(RucksackError::NoSharedItem { line: self.line }),
+            (Some(_), Some(_)) => Err(RucksackError::SeveralSharedItems {
+                line: self.line,
+                items: common.iter().collect(),
+            }),
+        }
+    }
+}
+
+pub fn get_common_items_across_rucksacks(rs: &[Rucksack]) -> Result<ItemSet, NoPriority> {
+    let mut sets = rs.iter().map(|r| ItemSet::try_from(r.items()));
+
+    match sets.next() {
+        Some(first) => sets.try_fold(first?, |acc, set| Ok(acc.intersection(set?))),
+        None => Ok(ItemSet::default()),
+    }
+}
+
+pub fn get_rucksacks(contents: &str) -> Result<Vec<Rucksack<'_>>, RucksackError> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Rucksack::new(line, i + 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        assert!(Rucksack::new("vJrwpWtwJgWrhcsFMMfFFhFp", 1).is_ok());
+
+        assert_eq!(
+            Rucksack::new("abc", 3).err(),
+            Some(RucksackError::OddLength { line: 3, length: 3 })
+        );
+        assert_eq!(
+            Rucksack::new("ab1c", 2).err(),
+            Some(RucksackError::InvalidItem {
+                line: 2,
+                column: 3,
+                item: '1'
+            })
+        );
+        assert_eq!(
+            get_rucksacks("abab\nab cd").err(),
+            Some(RucksackError::InvalidItem {
+                line: 2,
+                column: 3,
+                item: ' '
+            })
+        );
+    }
+
+    #[test]
+    fn shared_items() {
+        let rucksacks = get_rucksacks("vJrwpWtwJgWrhcsFMMfFFhFp\nabcd\nabab").unwrap();
+
+        assert_eq!(rucksacks[0].get_shared_item(), Ok('p'));
+        assert_eq!(
+            rucksacks[1].get_shared_item(),
+            Err(RucksackError::NoSharedItem { line: 2 })
+        );
+        assert_eq!(
+            rucksacks[2].get_shared_item(),
+            Err(RucksackError::SeveralSharedItems {
+                line: 3,
+                items: "ab".to_string()
+            })
+        );
+    }
+}
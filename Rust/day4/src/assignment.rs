@@ -0,0 +1,164 @@
+use std::num::ParseIntError;
+
+use thiserror::Error;
+
+use crate::interval::{Interval, IntervalSet};
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("Error when parsing int")]
+    ParseIntError(#[from] ParseIntError),
+
+    #[error("Incorrect format")]
+    IncorrectFormat,
+}
+
+/// Parses a line of comma-separated assignments such as `2-4,6-8,3-5`.
+pub fn parse_assignments(line: &str) -> Result<Vec<Interval>, ParseError> {
+    line.split(',')
+        .map(|assignment| {
+            let (start, end) = assignment
+                .split_once('-')
+                .ok_or(ParseError::IncorrectFormat)?;
+
+            Ok(Interval {
+                start: start.parse()?,
+                end: end.parse()?,
+            })
+        })
+        .collect()
+}
+
+/// How the assignments of a single line relate to each other. Assignments are
+/// referred to by their position in the line, starting from 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineReport {
+    /// Pairs `(i, j)` where assignment `i` fully contains assignment `j`. Identical
+    /// assignments show up both ways.
+    pub containing: Vec<(usize, usize)>,
+    /// Pairs `(i, j)`, with `i < j`, of assignments sharing at least one section.
+    pub overlapping: Vec<(usize, usize)>,
+    /// Sections between the first and last assigned ones that nobody covers.
+    pub uncovered: IntervalSet,
+    /// Largest number of assignments covering the same section.
+    pub max_depth: usize,
+}
+
+/// Smallest interval covering every assignment.
+pub fn hull(assignments: &[Interval]) -> Option<Interval> {
+    let start = assignments.iter().map(|i| i.start).min()?;
+    let end = assignments.iter().map(|i| i.end).max()?;
+
+    Some(Interval { start, end })
+}
+
+/// Largest number of assignments covering the same section, found by sweeping over
+/// the sorted starts and ends.
+pub fn max_depth(assignments: &[Interval]) -> usize {
+    // An assignment stops counting right after its last section, so that ends are
+    // processed before starts at the same position.
+    let mut events: Vec<(i64, i32)> = assignments
+        .iter()
+        .filter(|i| !i.is_empty())
+        .flat_map(|i| [(i64::from(i.start), 1), (i64::from(i.end) + 1, -1)])
+        .collect();
+    events.sort_unstable();
+
+    let mut depth = 0;
+    let mut max_depth = 0;
+
+    for (_, delta) in events {
+        depth += delta;
+        max_depth = max_depth.max(depth);
+    }
+
+    max_depth as usize
+}
+
+pub fn analyze_line(assignments: &[Interval]) -> LineReport {
+    let mut containing = Vec::new();
+    let mut overlapping = Vec::new();
+
+    for (i, a) in assignments.iter().enumerate() {
+        for (j, b) in assignments.iter().enumerate().skip(i + 1) {
+            if a.contains(b) {
+                containing.push((i, j));
+            }
+            if b.contains(a) {
+                containing.push((j, i));
+            }
+            if a.overlaps(b) {
+                overlapping.push((i, j));
+            }
+        }
+    }
+
+    containing.sort_unstable();
+
+    let uncovered = match hull(assignments) {
+        Some(hull) => {
+            IntervalSet::from_iter([hull]).difference(&assignments.iter().copied().collect())
+        }
+        None => IntervalSet::new(),
+    };
+
+    LineReport {
+        containing,
+        overlapping,
+        uncovered,
+        max_depth: max_depth(assignments),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        assert_eq!(
+            parse_assignments("2-4,6-8,3-5").unwrap(),
+            [
+                Interval::new(2, 4),
+                Interval::new(6, 8),
+                Interval::new(3, 5)
+            ]
+        );
+        assert_eq!(parse_assignments("7-9").unwrap(), [Interval::new(7, 9)]);
+
+        assert!(parse_assignments("2-4,").is_err());
+        assert!(parse_assignments("2-4;6-8").is_err());
+        assert!(parse_assignments("").is_err());
+    }
+
+    #[test]
+    fn report() {
+        let assignments = parse_assignments("2-8,3-4,6-6,12-14,3-4").unwrap();
+        let report = analyze_line(&assignments);
+
+        assert_eq!(report.containing, [(0, 1), (0, 2), (0, 4), (1, 4), (4, 1)]);
+        assert_eq!(report.overlapping, [(0, 1), (0, 2), (0, 4), (1, 4)]);
+        assert_eq!(
+            report.uncovered,
+            IntervalSet::from_iter([Interval::new(9, 11)])
+        );
+        assert_eq!(report.max_depth, 3);
+    }
+
+    #[test]
+    fn depth() {
+        assert_eq!(max_depth(&[]), 0);
+        // Touching assignments don't share a section.
+        assert_eq!(max_depth(&[Interval::new(1, 3), Interval::new(4, 6)]), 1);
+        assert_eq!(max_depth(&[Interval::new(1, 3), Interval::new(3, 6)]), 2);
+        assert_eq!(
+            max_depth(&[
+                Interval::new(1, 10),
+                Interval::new(2, 3),
+                Interval::new(5, 8),
+                Interval::new(6, 6)
+            ]),
+            3
+        );
+    }
+}
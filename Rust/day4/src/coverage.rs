@@ -0,0 +1,297 @@
+use std::cmp::Reverse;
+
+use crate::{
+    assignment::{parse_assignments, ParseError},
+    interval::{Interval, IntervalSet},
+};
+
+/// A single elf's assignment within the whole file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elf {
+    /// Line of the assignment in the input, numbered from 1.
+    pub line: usize,
+    /// Position of the assignment in its line, starting from 0.
+    pub position: usize,
+    pub sections: Interval,
+}
+
+pub fn get_elves(contents: &str) -> Result<Vec<Elf>, ParseError> {
+    let mut elves = Vec::new();
+
+    for (i, line) in contents.lines().enumerate() {
+        for (position, sections) in parse_assignments(line)?.into_iter().enumerate() {
+            elves.push(Elf {
+                line: i + 1,
+                position,
+                sections,
+            });
+        }
+    }
+
+    Ok(elves)
+}
+
+/// Node of a centered interval tree: it holds the elves covering `center`, while
+/// those entirely before or after it go to the `left` and `right` subtrees.
+struct Node {
+    center: i32,
+    /// The elves covering `center`, by increasing first section.
+    by_start: Vec<Elf>,
+    /// The same elves, by decreasing last section.
+    by_end: Vec<Elf>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// Index over every assignment of a file, answering coverage queries without going
+/// through all the elves each time.
+pub struct Coverage {
+    /// Elves sorted by the first section of their assignment.
+    elves: Vec<Elf>,
+    /// Interval tree over the elves, the root being the first node.
+    nodes: Vec<Node>,
+    /// Sorted, disjoint runs of sections along with how many elves cover them.
+    /// Sections covered by nobody are left out.
+    depths: Vec<(Interval, usize)>,
+}
+
+impl Coverage {
+    pub fn new(elves: &[Elf]) -> Self {
+        let mut elves: Vec<Elf> = elves
+            .iter()
+            .filter(|elf| !elf.sections.is_empty())
+            .copied()
+            .collect();
+        elves.sort_by_key(|elf| elf.sections.start);
+
+        // Sweep over the starts and the positions right after the ends: the depth
+        // only changes there.
+        let mut events: Vec<(i64, i32)> = elves
+            .iter()
+            .flat_map(|elf| {
+                [
+                    (i64::from(elf.sections.start), 1),
+                    (i64::from(elf.sections.end) + 1, -1),
+                ]
+            })
+            .collect();
+        events.sort_unstable();
+
+        let mut depths = Vec::new();
+        let mut depth = 0;
+
+        for (k, &(position, delta)) in events.iter().enumerate() {
+            depth += delta;
+
+            match events.get(k + 1) {
+                Some(&(next, _)) if depth > 0 && next > position => depths.push((
+                    Interval {
+                        start: position as i32,
+                        end: (next - 1) as i32,
+                    },
+                    depth as usize,
+                )),
+                _ => {}
+            }
+        }
+
+        let mut nodes = Vec::new();
+        build_tree(&mut nodes, elves.clone());
+
+        Self {
+            elves,
+            nodes,
+            depths,
+        }
+    }
+
+    /// Number of elves assigned to the section.
+    pub fn depth_at(&self, section: i32) -> usize {
+        let i = self
+            .depths
+            .partition_point(|(interval, _)| interval.end < section);
+
+        match self.depths.get(i) {
+            Some((interval, depth)) if interval.contains_point(section) => *depth,
+            _ => 0,
+        }
+    }
+
+    /// Elves assigned to the section, in no particular order.
+    ///
+    /// Only goes down a single branch of the interval tree, and stops going through
+    /// the elves of each node at the first one that doesn't cover the section.
+    pub fn covering(&self, section: i32) -> Vec<Elf> {
+        let mut elves = Vec::new();
+        let mut node = (!self.nodes.is_empty()).then_some(0);
+
+        while let Some(i) = node {
+            let Node {
+                center,
+                by_start,
+                by_end,
+                left,
+                right,
+            } = &self.nodes[i];
+
+            if section < *center {
+                elves.extend(by_start.iter().take_while(|e| e.sections.start <= section));
+                node = *left;
+            } else if section > *center {
+                elves.extend(by_end.iter().take_while(|e| e.sections.end >= section));
+                node = *right;
+            } else {
+                elves.extend(by_start);
+                node = None;
+            }
+        }
+
+        elves
+    }
+
+    /// Lines with at least one elf assigned to the section.
+    pub fn lines_covering(&self, section: i32) -> Vec<usize> {
+        let mut lines: Vec<usize> = self.covering(section).iter().map(|elf| elf.line).collect();
+        lines.sort_unstable();
+        lines.dedup();
+
+        lines
+    }
+
+    /// Sections assigned to more than `k` elves.
+    pub fn covered_more_than(&self, k: usize) -> IntervalSet {
+        self.depths
+            .iter()
+            .filter(|(_, depth)| *depth > k)
+            .map(|(interval, _)| *interval)
+            .collect()
+    }
+
+    /// Smallest set of elves to take off their assignment so that no section is
+    /// assigned twice, sorted by line and position.
+    ///
+    /// Keeps as many elves as possible by always keeping the one that finishes first
+    /// among those that don't clash with the ones already kept.
+    pub fn min_removals(&self) -> Vec<Elf> {
+        let mut by_end = self.elves.clone();
+        by_end.sort_by_key(|elf| elf.sections.end);
+
+        let mut last_kept_end = None;
+        let mut removed = Vec::new();
+
+        for elf in by_end {
+            if last_kept_end.is_some_and(|end| elf.sections.start <= end) {
+                removed.push(elf);
+            } else {
+                last_kept_end = Some(elf.sections.end);
+            }
+        }
+
+        removed.sort_by_key(|elf| (elf.line, elf.position));
+
+        removed
+    }
+}
+
+/// Adds the subtree holding the elves to `nodes`, returning the index of its root.
+///
+/// Centering each node on the median first section leaves at most half of the elves
+/// to each subtree, so the tree has a logarithmic depth.
+fn build_tree(nodes: &mut Vec<Node>, mut elves: Vec<Elf>) -> Option<usize> {
+    if elves.is_empty() {
+        return None;
+    }
+
+    let median = elves.len() / 2;
+    let center = elves
+        .select_nth_unstable_by_key(median, |elf| elf.sections.start)
+        .1
+        .sections
+        .start;
+
+    let (before, rest): (Vec<Elf>, Vec<Elf>) =
+        elves.into_iter().partition(|elf| elf.sections.end < center);
+    let (after, mut by_start): (Vec<Elf>, Vec<Elf>) = rest
+        .into_iter()
+        .partition(|elf| elf.sections.start > center);
+
+    by_start.sort_by_key(|elf| elf.sections.start);
+    let mut by_end = by_start.clone();
+    by_end.sort_by_key(|elf| Reverse(elf.sections.end));
+
+    let i = nodes.len();
+    nodes.push(Node {
+        center,
+        by_start,
+        by_end,
+        left: None,
+        right: None,
+    });
+
+    nodes[i].left = build_tree(nodes, before);
+    nodes[i].right = build_tree(nodes, after);
+
+    Some(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST1: &str = include_str!("../test_input1.txt");
+
+    #[test]
+    fn queries() {
+        let coverage = Coverage::new(&get_elves(TEST1).unwrap());
+
+        assert_eq!(coverage.lines_covering(4), [1, 2, 4, 5, 6]);
+        assert_eq!(coverage.lines_covering(1), Vec::<usize>::new());
+        assert_eq!(coverage.depth_at(6), 8);
+        assert_eq!(coverage.depth_at(9), 1);
+        assert_eq!(coverage.depth_at(10), 0);
+
+        assert_eq!(
+            coverage.covered_more_than(4),
+            IntervalSet::from_iter([Interval::new(3, 7)])
+        );
+        assert_eq!(
+            coverage.covered_more_than(6),
+            IntervalSet::from_iter([Interval::new(4, 6)])
+        );
+        assert_eq!(coverage.covered_more_than(8), IntervalSet::new());
+    }
+
+    #[test]
+    fn covering() {
+        let elves = get_elves(&[TEST1; 3].join("\n")).unwrap();
+        let coverage = Coverage::new(&elves);
+
+        for section in 0..=10 {
+            let mut covering = coverage.covering(section);
+            covering.sort_by_key(|elf| (elf.line, elf.position));
+
+            let expected: Vec<Elf> = elves
+                .iter()
+                .filter(|elf| elf.sections.contains_point(section))
+                .copied()
+                .collect();
+
+            assert_eq!(covering, expected, "section {section}");
+        }
+    }
+
+    #[test]
+    fn removals() {
+        let elves = get_elves("1-3,2-5\n4-4,6-8\n1-1").unwrap();
+        let removed = Coverage::new(&elves).min_removals();
+
+        // Dropping 1-3 and 2-5 leaves 1, 4 and 6-8 apart.
+        assert_eq!(
+            removed.iter().map(|elf| elf.sections).collect::<Vec<_>>(),
+            [Interval::new(1, 3), Interval::new(2, 5)]
+        );
+
+        let kept: Vec<Elf> = elves.into_iter().filter(|e| !removed.contains(e)).collect();
+        assert!(Coverage::new(&kept).covered_more_than(1).is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+/// Closed range of sections, with both ends included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i32,
+    pub end: i32,
+}
+
+impl Interval {
+    pub fn new(start: i32, end: i32) -> Self {
+        Self { start, end }
+    }
+
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        (self.start >= other.start && self.start <= other.end)
+            || (self.end >= other.start && self.end <= other.end)
+            || (other.start >= self.start && other.start <= self.end)
+            || (other.end >= self.start && other.end <= self.end)
+    }
+
+    pub fn contains_point(&self, point: i32) -> bool {
+        self.start <= point && point <= self.end
+    }
+
+    /// Number of sections in the interval.
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            (i64::from(self.end) - i64::from(self.start) + 1) as u64
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let intersection = Interval {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        };
+
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Joins two intervals, as long as they overlap or are right next to each other,
+    /// so that the result covers exactly the sections of both.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+
+        let touches = i64::from(self.start) <= i64::from(other.end) + 1
+            && i64::from(other.start) <= i64::from(self.end) + 1;
+
+        touches.then(|| Interval {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+}
+
+/// Set of sections, stored as sorted intervals that neither overlap nor touch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The normalized intervals, in increasing order.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        *self = self.union(&IntervalSet::from_iter([interval]));
+    }
+
+    pub fn contains_point(&self, point: i32) -> bool {
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.end < point);
+
+        self.intervals
+            .get(i)
+            .is_some_and(|interval| interval.contains_point(point))
+    }
+
+    /// Total number of sections in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intervals.extend(a.intersection(b));
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// Sections of `self` that are not in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+
+        for interval in &self.intervals {
+            // Intervals of `other` that end before this one starts can't matter for
+            // the following ones either.
+            while other
+                .intervals
+                .get(j)
+                .is_some_and(|b| b.end < interval.start)
+            {
+                j += 1;
+            }
+
+            // Start of the part of the interval not yet covered by `other`, if any.
+            let mut start = Some(interval.start);
+
+            for b in other.intervals[j..]
+                .iter()
+                .take_while(|b| b.start <= interval.end)
+            {
+                if let Some(s) = start.filter(|&s| s < b.start) {
+                    intervals.push(Interval {
+                        start: s,
+                        end: b.start - 1,
+                    });
+                }
+
+                start = b.end.checked_add(1);
+            }
+
+            if let Some(s) = start.filter(|&s| s <= interval.end) {
+                intervals.push(Interval {
+                    start: s,
+                    end: interval.end,
+                });
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    /// Sorts the intervals and merges the ones that overlap or touch. Empty intervals
+    /// are dropped.
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        let mut sorted: Vec<Interval> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        sorted.sort();
+
+        let mut intervals: Vec<Interval> = Vec::with_capacity(sorted.len());
+
+        for interval in sorted {
+            match intervals.last_mut() {
+                Some(last) => match last.union(&interval) {
+                    Some(merged) => *last = merged,
+                    None => intervals.push(interval),
+                },
+                None => intervals.push(interval),
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(s, e)| Interval::new(s, e))
+            .collect()
+    }
+
+    #[test]
+    fn interval_operations() {
+        assert_eq!(
+            Interval::new(2, 6).intersection(&Interval::new(4, 8)),
+            Some(Interval::new(4, 6))
+        );
+        assert_eq!(Interval::new(2, 4).intersection(&Interval::new(6, 8)), None);
+        assert_eq!(
+            Interval::new(2, 4).union(&Interval::new(5, 8)),
+            Some(Interval::new(2, 8))
+        );
+        assert_eq!(Interval::new(2, 4).union(&Interval::new(6, 8)), None);
+        assert_eq!(Interval::new(3, 7).len(), 5);
+        assert_eq!(Interval::new(i32::MIN, i32::MAX).len(), 1 << 32);
+    }
+
+    #[test]
+    fn normalization() {
+        let s = set(&[(5, 7), (1, 2), (3, 3), (10, 12), (11, 11), (8, 4)]);
+
+        assert_eq!(
+            s.intervals(),
+            &[
+                Interval::new(1, 3),
+                Interval::new(5, 7),
+                Interval::new(10, 12)
+            ]
+        );
+        assert_eq!(s.len(), 9);
+        assert!(s.contains_point(6));
+        assert!(!s.contains_point(4));
+        assert!(!s.contains_point(13));
+
+        let mut s = s;
+        s.insert(Interval::new(4, 4));
+        assert_eq!(s.intervals(), &[Interval::new(1, 7), Interval::new(10, 12)]);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(1, 5), (10, 15), (20, 25)]);
+        let b = set(&[(4, 11), (14, 21)]);
+
+        assert_eq!(a.union(&b), set(&[(1, 25)]));
+        assert_eq!(
+            a.intersection(&b),
+            set(&[(4, 5), (10, 11), (14, 15), (20, 21)])
+        );
+        assert_eq!(a.difference(&b), set(&[(1, 3), (12, 13), (22, 25)]));
+        assert_eq!(b.difference(&a), set(&[(6, 9), (16, 19)]));
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(a.difference(&IntervalSet::new()), a);
+        assert_eq!(
+            set(&[(1, 10)]).difference(&set(&[(3, 4), (6, 7)])),
+            set(&[(1, 2), (5, 5), (8, 10)])
+        );
+    }
+}
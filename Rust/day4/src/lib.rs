@@ -0,0 +1,3 @@
+pub mod assignment;
+pub mod coverage;
+pub mod interval;
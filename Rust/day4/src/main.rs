@@ -1,28 +1,14 @@
 use std::num::ParseIntError;
 
+use day4::{
+    assignment::{analyze_line, parse_assignments, ParseError},
+    coverage::{get_elves, Coverage},
+    interval::{Interval, IntervalSet},
+};
 use thiserror::Error;
 
 const INPUT: &str = include_str!("../input.txt");
 
-#[derive(Debug, PartialEq)]
-struct Interval {
-    start: i32,
-    end: i32,
-}
-
-impl Interval {
-    fn contains(&self, other: &Interval) -> bool {
-        self.start <= other.start && self.end >= other.end
-    }
-
-    fn overlaps(&self, other: &Interval) -> bool {
-        (self.start >= other.start && self.start <= other.end)
-            || (self.end >= other.start && self.end <= other.end)
-            || (other.start >= self.start && other.start <= self.end)
-            || (other.end >= self.start && other.end <= self.end)
-    }
-}
-
 fn get_intervals(file_contents: &str) -> Result<Vec<(Interval, Interval)>, ParseError> {
     file_contents.lines().map(parse_line).collect()
 }
@@ -43,39 +29,83 @@ fn solve_part_two(file_contents: &str) -> Result<usize, ParseError> {
 }
 
 fn parse_line(line: &str) -> Result<(Interval, Interval), ParseError> {
-    let split_line: Vec<&str> = line.split(&[',', '-']).collect();
-
-    match split_line[..] {
-        [a1, a2, b1, b2] => Ok((
-            Interval {
-                start: a1.parse()?,
-                end: a2.parse()?,
-            },
-            Interval {
-                start: b1.parse()?,
-                end: b2.parse()?,
-            },
-        )),
+    match parse_assignments(line)?[..] {
+        [a, b] => Ok((a, b)),
         _ => Err(ParseError::IncorrectFormat),
     }
 }
 
-#[derive(Error, Debug)]
-enum ParseError {
-    #[error("Error when parsing int")]
-    ParseIntError(#[from] ParseIntError),
+fn print_reports(file_contents: &str) -> Result<(), ParseError> {
+    for (i, line) in file_contents.lines().enumerate() {
+        let report = analyze_line(&parse_assignments(line)?);
+        let uncovered: Vec<String> = report
+            .uncovered
+            .intervals()
+            .iter()
+            .map(|interval| format!("{}-{}", interval.start, interval.end))
+            .collect();
+
+        println!(
+            "Line {}: contains {:?}, overlaps {:?}, uncovered [{}], max depth {}",
+            i + 1,
+            report.containing,
+            report.overlapping,
+            uncovered.join(", "),
+            report.max_depth
+        );
+    }
 
-    #[error("Incorrect format")]
-    IncorrectFormat,
+    Ok(())
 }
 
-fn main() -> Result<(), ParseError> {
-    println!("{}", solve_part_one(INPUT)?);
-    println!("{}", solve_part_two(INPUT)?);
+fn print_sections(sections: &IntervalSet) {
+    for interval in sections.intervals() {
+        println!("{}-{}", interval.start, interval.end);
+    }
+}
+
+fn main() -> Result<(), ExecutionError> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args[..] {
+        ["analyze"] => print_reports(INPUT)?,
+        ["cover", section] => {
+            let coverage = Coverage::new(&get_elves(INPUT)?);
+            println!("{:?}", coverage.lines_covering(section.parse()?));
+        }
+        ["crowded", k] => {
+            print_sections(&Coverage::new(&get_elves(INPUT)?).covered_more_than(k.parse()?))
+        }
+        ["remove"] => {
+            for elf in Coverage::new(&get_elves(INPUT)?).min_removals() {
+                println!(
+                    "Line {}, elf {}: {}-{}",
+                    elf.line,
+                    elf.position + 1,
+                    elf.sections.start,
+                    elf.sections.end
+                );
+            }
+        }
+        _ => {
+            println!("{}", solve_part_one(INPUT)?);
+            println!("{}", solve_part_two(INPUT)?);
+        }
+    }
 
     Ok(())
 }
 
+#[derive(Error, Debug)]
+enum ExecutionError {
+    #[error("Error when parsing the input")]
+    ParseError(#[from] ParseError),
+
+    #[error("Error when parsing an argument")]
+    ParseArgumentError(#[from] ParseIntError),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,10 +117,11 @@ mod tests {
     fn test_parse_line() {
         assert_eq!(
             parse_line("2-4,6-8").unwrap(),
-            (Interval { start: 2, end: 4 }, Interval { start: 6, end: 8 })
+            (Interval::new(2, 4), Interval::new(6, 8))
         );
 
         assert!(parse_line("2-4,6-12a").is_err());
+        assert!(parse_line("2-4,6-8,1-3").is_err());
         assert!(parse_line("").is_err());
     }
 
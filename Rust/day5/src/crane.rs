@@ -1,11 +1,14 @@
+use std::fmt;
+
 use thiserror::Error;
 
-#[derive(Debug)]
-pub struct CraneStacks {
-    pub stacks: Vec<Vec<char>>,
+/// Stacks of crates, each crate being identified by a label of type `T`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CraneStacks<T = char> {
+    pub stacks: Vec<Vec<T>>,
 }
 
-impl CraneStacks {
+impl<T> CraneStacks<T> {
     pub fn new_with_stacks(n_stacks: usize) -> Self {
         let mut stacks = vec![];
 
@@ -16,44 +19,196 @@ impl CraneStacks {
         Self { stacks }
     }
 
-    pub fn mov(&mut self, movement: Movement) -> Result<(), MovementError> {
-        for _ in 0..movement.amount {
-            let item = self.stacks[movement.origin - 1]
-                .pop()
-                .ok_or(MovementError)?;
+    fn stack(&self, stack: usize) -> Result<&Vec<T>, InvalidMovement> {
+        stack
+            .checked_sub(1)
+            .and_then(|i| self.stacks.get(i))
+            .ok_or(InvalidMovement::NoSuchStack {
+                stack,
+                n_stacks: self.stacks.len(),
+            })
+    }
+
+    /// Checks that both stacks of the movement exist, which doesn't depend on the
+    /// movements applied before it.
+    pub fn check_stacks(&self, movement: &Movement) -> Result<(), InvalidMovement> {
+        self.stack(movement.origin)?;
+        self.stack(movement.destination)?;
+
+        Ok(())
+    }
 
-            self.stacks[movement.destination - 1].push(item);
+    /// Checks that both stacks of the movement exist and that the origin holds
+    /// enough crates, without changing anything.
+    pub fn check(&self, movement: &Movement) -> Result<(), InvalidMovement> {
+        self.check_stacks(movement)?;
+        let available = self.stack(movement.origin)?.len();
+
+        if movement.amount > available {
+            return Err(InvalidMovement::NotEnoughCrates { available });
         }
 
         Ok(())
     }
 
-    pub fn mov_9001(&mut self, movement: Movement) -> Result<(), MovementError> {
-        let amount = movement.amount;
-        let origin_len = self.stacks[movement.origin - 1].len();
+    /// Takes the `amount` top crates of a stack, numbered from 1, listed from the
+    /// bottom one to the top one. Nothing is taken if there aren't enough crates.
+    pub fn take(&mut self, stack: usize, amount: usize) -> Result<Vec<T>, InvalidMovement> {
+        let available = self.stack(stack)?.len();
 
-        if amount > origin_len {
-            return Err(MovementError);
+        if amount > available {
+            return Err(InvalidMovement::NotEnoughCrates { available });
         }
 
-        let stacks = &mut self.stacks;
+        Ok(self.stacks[stack - 1].split_off(available - amount))
+    }
 
-        let items: Vec<char> = stacks[movement.origin - 1]
-            .drain((origin_len - amount)..)
-            .collect();
+    /// Puts crates on top of a stack, numbered from 1, the last one ending on top.
+    ///
+    /// Panics if the stack doesn't exist, which [`CraneStacks::check`] rules out.
+    pub fn put(&mut self, stack: usize, crates: impl IntoIterator<Item = T>) {
+        self.stacks[stack - 1].extend(crates);
+    }
+}
 
-        stacks[movement.destination - 1].extend(items);
+impl<T: fmt::Display> CraneStacks<T> {
+    /// Crate on top of each stack, skipping empty stacks.
+    pub fn top_of_stacks(&self) -> String {
+        self.stacks
+            .iter()
+            .flat_map(|stack| stack.last())
+            .map(T::to_string)
+            .collect()
+    }
+}
 
-        Ok(())
+/// Draws the stacks like in the puzzle input, with the highest crates first and the
+/// numbered base line last, without trailing spaces. Columns are made wide enough
+/// for the longest label.
+impl<T: fmt::Display> fmt::Display for CraneStacks<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let width = self
+            .stacks
+            .iter()
+            .flatten()
+            .map(|c| c.to_string().chars().count() + 2)
+            .chain([3, self.stacks.len().to_string().len()])
+            .max()
+            .unwrap_or(3);
+
+        for level in (0..height).rev() {
+            let row: Vec<String> = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("{:width$}", format!("[{c}]")),
+                    None => " ".repeat(width),
+                })
+                .collect();
+
+            writeln!(f, "{}", row.join(" ").trim_end())?;
+        }
+
+        let base: Vec<String> = (1..=self.stacks.len())
+            .map(|i| format!("{i:^width$}"))
+            .collect();
+
+        write!(f, "{}", base.join(" ").trim_end())
     }
 }
 
-#[derive(Debug, Error)]
-#[error("Movement error")]
-pub struct MovementError;
+/// Why a movement can't be carried out.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum InvalidMovement {
+    #[error("there is no stack {stack}, stacks are numbered from 1 to {n_stacks}")]
+    NoSuchStack { stack: usize, n_stacks: usize },
+
+    #[error("only {available} crates are available")]
+    NotEnoughCrates { available: usize },
 
+    #[error("the crane can't lift any crate")]
+    NoCapacity,
+}
+
+/// A movement of a list that couldn't be carried out. The stacks are left as they
+/// were before that movement.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+#[error("Move {index} (move {amount} from {origin} to {destination}): {reason}")]
+pub struct MovementError {
+    /// Position of the movement in the list, numbered from 1.
+    pub index: usize,
+    pub amount: usize,
+    pub origin: usize,
+    pub destination: usize,
+    pub reason: InvalidMovement,
+}
+
+impl MovementError {
+    pub fn new(index: usize, movement: Movement, reason: InvalidMovement) -> Self {
+        Self {
+            index,
+            amount: movement.amount,
+            origin: movement.origin,
+            destination: movement.destination,
+            reason,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Movement {
     pub amount: usize,
     pub origin: usize,
     pub destination: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movement(amount: usize, origin: usize, destination: usize) -> Movement {
+        Movement {
+            amount,
+            origin,
+            destination,
+        }
+    }
+
+    #[test]
+    fn checks() {
+        let stacks = CraneStacks {
+            stacks: vec![vec!['A', 'B'], vec![]],
+        };
+
+        assert_eq!(stacks.check(&movement(2, 1, 2)), Ok(()));
+        assert_eq!(
+            stacks.check(&movement(1, 0, 2)),
+            Err(InvalidMovement::NoSuchStack {
+                stack: 0,
+                n_stacks: 2
+            })
+        );
+        assert_eq!(
+            stacks.check(&movement(1, 1, 3)),
+            Err(InvalidMovement::NoSuchStack {
+                stack: 3,
+                n_stacks: 2
+            })
+        );
+        assert_eq!(
+            stacks.check(&movement(1, 2, 1)),
+            Err(InvalidMovement::NotEnoughCrates { available: 0 })
+        );
+
+        let error = MovementError::new(
+            7,
+            movement(3, 1, 2),
+            InvalidMovement::NotEnoughCrates { available: 2 },
+        );
+        assert_eq!(
+            error.to_string(),
+            "Move 7 (move 3 from 1 to 2): only 2 crates are available"
+        );
+    }
+}
@@ -1,2 +1,4 @@
 pub mod crane;
+pub mod model;
 pub mod parsing;
+pub mod session;
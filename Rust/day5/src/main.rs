@@ -1,37 +1,63 @@
+use std::num::ParseIntError;
+
 use day5::{
-    crane::MovementError,
+    crane::{Movement, MovementError},
+    model::{run, AlternatingCrane, CraneModel, CrateMover9000, CrateMover9001, LimitedCrane},
     parsing::{parse_file_contents, ParseError},
+    session::CraneSession,
 };
 use thiserror::Error;
 
 const INPUT: &str = include_str!("../input.txt");
 
-fn solve_part_one(contents: &str) -> Result<String, ExecutionError> {
+fn solve(contents: &str, model: &mut impl CraneModel) -> Result<String, ExecutionError> {
     let (mut crane_stacks, movements) = parse_file_contents(contents)?;
 
-    for movement in movements {
-        crane_stacks.mov(movement)?;
-    }
+    run(model, &mut crane_stacks, movements)?;
 
-    Ok(crane_stacks
-        .stacks
-        .iter()
-        .flat_map(|stack| stack.last())
-        .collect())
+    Ok(crane_stacks.top_of_stacks())
 }
 
-fn solve_part_two(contents: &str) -> Result<String, ExecutionError> {
-    let (mut crane_stacks, movements) = parse_file_contents(contents)?;
+/// Draws the stacks as they are after the first `n_moves` movements of part one.
+fn draw(contents: &str, n_moves: usize) -> Result<String, ExecutionError> {
+    let (crane_stacks, movements) = parse_file_contents(contents)?;
+    let mut session = CraneSession::new(CrateMover9000, crane_stacks, movements);
+
+    session.seek(n_moves)?;
 
-    for movement in movements {
-        crane_stacks.mov_9001(movement)?;
+    Ok(session.stacks().to_string())
+}
+
+/// Prints every movement of part one, along with the crates it moved and the top of
+/// the stacks right after.
+fn print_history(contents: &str) -> Result<(), ExecutionError> {
+    let (crane_stacks, movements) = parse_file_contents(contents)?;
+    let mut session = CraneSession::new(CrateMover9000, crane_stacks, movements);
+
+    while let Some(step) = session.redo()? {
+        let Movement {
+            amount,
+            origin,
+            destination,
+        } = step.movement;
+        let placed: String = step.placed.iter().collect();
+
+        println!(
+            "{}: move {amount} from {origin} to {destination}, placing {placed}, top {}",
+            session.position(),
+            session.top_of_stacks()
+        );
     }
 
-    Ok(crane_stacks
-        .stacks
-        .iter()
-        .flat_map(|stack| stack.last())
-        .collect())
+    Ok(())
+}
+
+fn solve_part_one(contents: &str) -> Result<String, ExecutionError> {
+    solve(contents, &mut CrateMover9000)
+}
+
+fn solve_part_two(contents: &str) -> Result<String, ExecutionError> {
+    solve(contents, &mut CrateMover9001)
 }
 
 #[derive(Error, Debug)]
@@ -41,11 +67,30 @@ enum ExecutionError {
 
     #[error("Movement error")]
     MovementError(#[from] MovementError),
+
+    #[error("Error when parsing an argument")]
+    InvalidArgument(#[from] ParseIntError),
 }
 
 fn main() -> Result<(), ExecutionError> {
-    println!("{}", solve_part_one(INPUT)?);
-    println!("{}", solve_part_two(INPUT)?);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args[..] {
+        ["limited", max_crates] => {
+            let mut model = LimitedCrane {
+                max_crates: max_crates.parse()?,
+            };
+            println!("{}", solve(INPUT, &mut model)?);
+        }
+        ["draw", n_moves] => println!("{}", draw(INPUT, n_moves.parse()?)?),
+        ["history"] => print_history(INPUT)?,
+        ["alternating"] => println!("{}", solve(INPUT, &mut AlternatingCrane::default())?),
+        _ => {
+            println!("{}", solve_part_one(INPUT)?);
+            println!("{}", solve_part_two(INPUT)?);
+        }
+    }
 
     Ok(())
 }
@@ -65,4 +110,26 @@ mod tests {
     fn part_two() {
         assert_eq!(solve_part_two(TEST).unwrap(), "MCD");
     }
+
+    #[test]
+    fn draw_intermediate_state() {
+        assert_eq!(
+            draw(TEST, 2).unwrap(),
+            "        [Z]\n        [N]\n    [C] [D]\n    [M] [P]\n 1   2   3"
+        );
+    }
+
+    #[test]
+    fn other_models() {
+        assert_eq!(
+            solve(TEST, &mut LimitedCrane { max_crates: 1 }).unwrap(),
+            "CMZ"
+        );
+        assert_eq!(
+            solve(TEST, &mut LimitedCrane { max_crates: 3 }).unwrap(),
+            "MCD"
+        );
+        assert!(solve(TEST, &mut LimitedCrane { max_crates: 2 }).is_ok());
+        assert!(solve(TEST, &mut AlternatingCrane::default()).is_ok());
+    }
 }
@@ -0,0 +1,236 @@
+use crate::crane::{CraneStacks, InvalidMovement, Movement, MovementError};
+
+/// How a crane carries out a movement.
+///
+/// Callers check the movement with [`CraneStacks::check`] beforehand, so that
+/// implementations can rely on both stacks existing and on the origin holding enough
+/// crates. Implementations with limits of their own check them before touching the
+/// stacks, so that an invalid movement leaves them unchanged.
+pub trait CraneModel {
+    fn apply<T>(
+        &mut self,
+        stacks: &mut CraneStacks<T>,
+        movement: Movement,
+    ) -> Result<(), InvalidMovement>;
+}
+
+/// Moves crates one at a time, which reverses their order.
+pub struct CrateMover9000;
+
+/// Moves all the crates at once, keeping their order.
+pub struct CrateMover9001;
+
+/// Moves crates in lifts of at most `max_crates`, each lift keeping its order.
+pub struct LimitedCrane {
+    pub max_crates: usize,
+}
+
+/// Moves all the crates at once, but reverses them on every other lift, starting
+/// with the second one.
+#[derive(Default)]
+pub struct AlternatingCrane {
+    lifts: usize,
+}
+
+impl CraneModel for CrateMover9000 {
+    fn apply<T>(
+        &mut self,
+        stacks: &mut CraneStacks<T>,
+        movement: Movement,
+    ) -> Result<(), InvalidMovement> {
+        let crates = stacks.take(movement.origin, movement.amount)?;
+        stacks.put(movement.destination, crates.into_iter().rev());
+
+        Ok(())
+    }
+}
+
+impl CraneModel for CrateMover9001 {
+    fn apply<T>(
+        &mut self,
+        stacks: &mut CraneStacks<T>,
+        movement: Movement,
+    ) -> Result<(), InvalidMovement> {
+        let crates = stacks.take(movement.origin, movement.amount)?;
+        stacks.put(movement.destination, crates);
+
+        Ok(())
+    }
+}
+
+impl CraneModel for LimitedCrane {
+    fn apply<T>(
+        &mut self,
+        stacks: &mut CraneStacks<T>,
+        movement: Movement,
+    ) -> Result<(), InvalidMovement> {
+        if self.max_crates == 0 && movement.amount > 0 {
+            return Err(InvalidMovement::NoCapacity);
+        }
+
+        let mut remaining = movement.amount;
+
+        while remaining > 0 {
+            let lifted = remaining.min(self.max_crates);
+            let crates = stacks.take(movement.origin, lifted)?;
+            stacks.put(movement.destination, crates);
+
+            remaining -= lifted;
+        }
+
+        Ok(())
+    }
+}
+
+impl CraneModel for AlternatingCrane {
+    fn apply<T>(
+        &mut self,
+        stacks: &mut CraneStacks<T>,
+        movement: Movement,
+    ) -> Result<(), InvalidMovement> {
+        let crates = stacks.take(movement.origin, movement.amount)?;
+
+        if self.lifts % 2 == 1 {
+            stacks.put(movement.destination, crates.into_iter().rev());
+        } else {
+            stacks.put(movement.destination, crates);
+        }
+
+        self.lifts += 1;
+
+        Ok(())
+    }
+}
+
+/// Applies every movement in order with the given crane, stopping at the first one
+/// that can't be carried out.
+///
+/// Movements between stacks that don't exist are rejected before anything is
+/// applied, while running out of crates only shows up once the earlier movements
+/// were carried out.
+pub fn run<T>(
+    model: &mut impl CraneModel,
+    stacks: &mut CraneStacks<T>,
+    movements: impl IntoIterator<Item = Movement>,
+) -> Result<(), MovementError> {
+    let movements: Vec<Movement> = movements.into_iter().collect();
+
+    for (i, movement) in movements.iter().enumerate() {
+        stacks
+            .check_stacks(movement)
+            .map_err(|reason| MovementError::new(i + 1, *movement, reason))?;
+    }
+
+    movements
+        .into_iter()
+        .enumerate()
+        .try_for_each(|(i, movement)| {
+            stacks
+                .check(&movement)
+                .and_then(|()| model.apply(stacks, movement))
+                .map_err(|reason| MovementError::new(i + 1, movement, reason))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stacks() -> CraneStacks {
+        CraneStacks {
+            stacks: vec!["ABCDE".chars().collect(), vec![]],
+        }
+    }
+
+    fn movement(amount: usize) -> Movement {
+        Movement {
+            amount,
+            origin: 1,
+            destination: 2,
+        }
+    }
+
+    fn apply(model: &mut impl CraneModel, movements: &[Movement]) -> Vec<String> {
+        let mut stacks = stacks();
+        run(model, &mut stacks, movements.iter().copied()).unwrap();
+
+        stacks.stacks.iter().map(|s| s.iter().collect()).collect()
+    }
+
+    #[test]
+    fn models() {
+        assert_eq!(apply(&mut CrateMover9000, &[movement(4)]), ["A", "EDCB"]);
+        assert_eq!(apply(&mut CrateMover9001, &[movement(4)]), ["A", "BCDE"]);
+        assert_eq!(
+            apply(&mut LimitedCrane { max_crates: 3 }, &[movement(4)]),
+            ["A", "CDEB"]
+        );
+        // Same as the 9000 with a single crate per lift, and as the 9001 without a
+        // limit.
+        assert_eq!(
+            apply(&mut LimitedCrane { max_crates: 1 }, &[movement(4)]),
+            ["A", "EDCB"]
+        );
+        assert_eq!(
+            apply(
+                &mut AlternatingCrane::default(),
+                &[movement(2), movement(2)]
+            ),
+            ["A", "DECB"]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut stacks = stacks();
+
+        // Missing stacks are found before any movement is applied.
+        let to_nowhere = Movement {
+            destination: 3,
+            ..movement(1)
+        };
+        assert_eq!(
+            run(&mut CrateMover9000, &mut stacks, [movement(1), to_nowhere]),
+            Err(MovementError::new(
+                2,
+                to_nowhere,
+                InvalidMovement::NoSuchStack {
+                    stack: 3,
+                    n_stacks: 2
+                }
+            ))
+        );
+        assert_eq!(stacks.stacks[0].len(), 5);
+
+        assert_eq!(
+            run(&mut CrateMover9001, &mut stacks, [movement(6)]),
+            Err(MovementError::new(
+                1,
+                movement(6),
+                InvalidMovement::NotEnoughCrates { available: 5 }
+            ))
+        );
+        assert_eq!(
+            LimitedCrane { max_crates: 0 }.apply(&mut stacks, movement(1)),
+            Err(InvalidMovement::NoCapacity)
+        );
+
+        // The last movement fails, the ones before it stay applied.
+        let error = run(
+            &mut LimitedCrane { max_crates: 2 },
+            &mut stacks,
+            [movement(2), movement(4)],
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            MovementError::new(
+                2,
+                movement(4),
+                InvalidMovement::NotEnoughCrates { available: 3 }
+            )
+        );
+        assert_eq!(stacks.stacks, [vec!['A', 'B', 'C'], vec!['D', 'E']]);
+    }
+}
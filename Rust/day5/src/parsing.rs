@@ -1,7 +1,17 @@
+use std::str::FromStr;
+
 use crate::crane::{CraneStacks, Movement};
 use thiserror::Error;
 
 pub fn parse_file_contents(contents: &str) -> Result<(CraneStacks, Vec<Movement>), ParseError> {
+    parse_file_contents_as(contents)
+}
+
+/// Parses the input with crates labelled by any type, such as `String` for labels
+/// longer than a single character.
+pub fn parse_file_contents_as<T: FromStr>(
+    contents: &str,
+) -> Result<(CraneStacks<T>, Vec<Movement>), ParseError> {
     let lines: Vec<&str> = contents.lines().collect();
 
     let empty_index = lines.iter().position(|x| x.is_empty()).ok_or(ParseError)?;
@@ -20,13 +30,87 @@ pub fn parse_file_contents(contents: &str) -> Result<(CraneStacks, Vec<Movement>
     Ok((crane_stacks, movements))
 }
 
-fn parse_line_to_crane(line: &str) -> Result<Vec<char>, ParseError> {
-    line.as_bytes()
-        .chunks(4)
-        .map(|x| x.get(1).map(|y| *y as char).ok_or(ParseError))
+/// Parses a drawing of the stacks on its own, without any movements.
+pub fn parse_drawing(drawing: &str) -> Result<CraneStacks, ParseError> {
+    parse_drawing_as(drawing)
+}
+
+pub fn parse_drawing_as<T: FromStr>(drawing: &str) -> Result<CraneStacks<T>, ParseError> {
+    build_crane(&drawing.lines().collect::<Vec<&str>>())
+}
+
+/// Middle of a token starting at byte `start`, doubled so that it stays whole.
+fn center(start: usize, token: &str) -> usize {
+    2 * start + token.len() - 1
+}
+
+/// Whitespace-separated tokens of a line, along with where they start.
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                tokens.push((s, &line[s..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    tokens
+}
+
+/// Crates of a line, from an opening bracket to the next closing one, along with
+/// where they start.
+fn crates(line: &str) -> Vec<(usize, &str)> {
+    line.match_indices('[')
+        .map(|(start, _)| match line[start..].find(']') {
+            Some(end) => (start, &line[start..=start + end]),
+            None => (start, &line[start..]),
+        })
         .collect()
 }
 
+/// Parses a row of crates, placing each one in the column whose number is closest
+/// to its middle. Labels can be of any length and the spacing doesn't need to be
+/// regular.
+fn parse_line_to_crane<T: FromStr>(
+    line: &str,
+    columns: &[usize],
+) -> Result<Vec<Option<T>>, ParseError> {
+    let mut row: Vec<Option<T>> = columns.iter().map(|_| None).collect();
+
+    // Anything that isn't part of a crate must be blank.
+    let mut rest = line.to_string();
+
+    for (start, token) in crates(line) {
+        let label = token
+            .strip_prefix('[')
+            .and_then(|token| token.strip_suffix(']'))
+            .ok_or(ParseError)?;
+
+        let column = (0..columns.len())
+            .min_by_key(|&i| columns[i].abs_diff(center(start, token)))
+            .ok_or(ParseError)?;
+
+        if row[column].is_some() {
+            return Err(ParseError);
+        }
+
+        row[column] = Some(label.parse().map_err(|_| ParseError)?);
+        rest.replace_range(start..start + token.len(), &" ".repeat(token.len()));
+    }
+
+    if !rest.trim().is_empty() {
+        return Err(ParseError);
+    }
+
+    Ok(row)
+}
+
 fn parse_line_to_movement(line: &str) -> Result<Movement, ParseError> {
     let line: Vec<&str> = line.split(' ').collect();
 
@@ -40,18 +124,37 @@ fn parse_line_to_movement(line: &str) -> Result<Movement, ParseError> {
     }
 }
 
-fn build_crane(lines: &[&str]) -> Result<CraneStacks, ParseError> {
-    let last_line = lines.last().ok_or(ParseError)?;
-    let n_stacks = last_line.split_whitespace().count();
+/// Builds the stacks from the drawing, finding the columns from the numbered base
+/// line, which must count from 1.
+fn build_crane<T: FromStr>(lines: &[&str]) -> Result<CraneStacks<T>, ParseError> {
+    let (last_line, rows) = lines.split_last().ok_or(ParseError)?;
+    let numbers = tokens(last_line);
 
-    let mut crane_stacks = CraneStacks::new_with_stacks(n_stacks);
+    for (i, (_, number)) in numbers.iter().enumerate() {
+        if number.parse() != Ok(i + 1) {
+            return Err(ParseError);
+        }
+    }
 
-    for line in lines[..(lines.len() - 1)].iter().rev() {
-        let line = parse_line_to_crane(line)?;
+    let columns: Vec<usize> = numbers
+        .iter()
+        .map(|&(start, number)| center(start, number))
+        .collect();
+    let mut crane_stacks = CraneStacks::new_with_stacks(columns.len());
+
+    for (level, line) in rows.iter().rev().enumerate() {
+        for (stack, c) in crane_stacks
+            .stacks
+            .iter_mut()
+            .zip(parse_line_to_crane(line, &columns)?)
+        {
+            if let Some(c) = c {
+                // A crate can't float above an empty spot.
+                if stack.len() != level {
+                    return Err(ParseError);
+                }
 
-        for (i, c) in line.iter().enumerate() {
-            if *c != ' ' {
-                crane_stacks.stacks[i].push(*c)
+                stack.push(c);
             }
         }
     }
@@ -62,3 +165,76 @@ fn build_crane(lines: &[&str]) -> Result<CraneStacks, ParseError> {
 #[derive(Debug, Error)]
 #[error("Parse error")]
 pub struct ParseError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn round_trip() {
+        let drawing = TEST.split("\n\n").next().unwrap();
+        let (crane_stacks, _) = parse_file_contents(TEST).unwrap();
+
+        assert_eq!(crane_stacks.to_string(), drawing);
+        assert_eq!(parse_drawing(drawing).unwrap(), crane_stacks);
+
+        let input = include_str!("../input.txt");
+        let (crane_stacks, _) = parse_file_contents(input).unwrap();
+
+        assert_eq!(
+            crane_stacks.to_string(),
+            input.split("\n\n").next().unwrap()
+        );
+    }
+
+    #[test]
+    fn labels_and_spacing() {
+        let drawing = "[AB]\n[C]   [DEF]\n 1      2";
+        let crane_stacks: CraneStacks<String> = parse_drawing_as(drawing).unwrap();
+
+        assert_eq!(crane_stacks.stacks, [vec!["C", "AB"], vec!["DEF"]]);
+        assert_eq!(crane_stacks.top_of_stacks(), "ABDEF");
+        // Longer labels don't fit in a char.
+        assert!(parse_drawing(drawing).is_err());
+
+        let rendered = crane_stacks.to_string();
+        assert_eq!(rendered, "[AB]\n[C]   [DEF]\n  1     2");
+        assert_eq!(parse_drawing_as::<String>(&rendered).unwrap(), crane_stacks);
+
+        // Crates close to their number, but not aligned with it.
+        let irregular = parse_drawing(" [A]\n [B]  [C]\n1    2").unwrap();
+        assert_eq!(irregular.stacks, [vec!['B', 'A'], vec!['C']]);
+
+        let numbered = parse_drawing_as::<u32>("[10] [7]\n 1    2").unwrap();
+        assert_eq!(numbered.stacks, [vec![10], vec![7]]);
+    }
+
+    #[test]
+    fn invalid_drawings() {
+        // Floating crate.
+        assert!(parse_drawing("[A]\n    [B]\n 1   2").is_err());
+        // Two crates in the same column.
+        assert!(parse_drawing("[A] [B]\n 1").is_err());
+        // Stray characters and badly numbered columns.
+        assert!(parse_drawing("[A] x\n 1   2").is_err());
+        assert!(parse_drawing("[A]\n 2").is_err());
+        assert!(parse_drawing("[A").is_err());
+    }
+
+    #[test]
+    fn render_after_moves() {
+        let crane_stacks = CraneStacks {
+            stacks: vec![vec![], vec!['A', 'B'], vec![]],
+        };
+        let drawing = crane_stacks.to_string();
+
+        assert_eq!(drawing, "    [B]\n    [A]\n 1   2   3");
+        assert_eq!(parse_drawing(&drawing).unwrap(), crane_stacks);
+        assert_eq!(
+            CraneStacks::<char>::new_with_stacks(2).to_string(),
+            " 1   2"
+        );
+    }
+}
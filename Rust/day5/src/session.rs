@@ -0,0 +1,231 @@
+use std::fmt;
+
+use crate::{
+    crane::{CraneStacks, Movement, MovementError},
+    model::CraneModel,
+};
+
+/// A movement that was applied, along with the crates it moved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step<T = char> {
+    pub movement: Movement,
+    /// Crates taken from the origin, from the bottom one to the top one.
+    pub taken: Vec<T>,
+    /// Crates put on the destination, from the bottom one to the top one.
+    pub placed: Vec<T>,
+}
+
+/// Goes through a list of movements one at a time, keeping track of what each one
+/// did so that it can be undone.
+///
+/// The crane model is only asked to carry out a movement the first time it is
+/// reached. Moving forward again after an undo replays the recorded crates, so that
+/// models keeping track of their lifts stay consistent.
+pub struct CraneSession<M: CraneModel, T = char> {
+    model: M,
+    stacks: CraneStacks<T>,
+    movements: Vec<Movement>,
+    /// Every movement applied so far, including the ones that were undone.
+    steps: Vec<Step<T>>,
+    /// Number of movements currently applied.
+    position: usize,
+}
+
+impl<M: CraneModel, T: Clone + fmt::Display> CraneSession<M, T> {
+    pub fn new(model: M, stacks: CraneStacks<T>, movements: Vec<Movement>) -> Self {
+        Self {
+            model,
+            stacks,
+            movements,
+            steps: Vec::new(),
+            position: 0,
+        }
+    }
+
+    pub fn stacks(&self) -> &CraneStacks<T> {
+        &self.stacks
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Total number of movements, applied or not.
+    pub fn len(&self) -> usize {
+        self.movements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.movements.is_empty()
+    }
+
+    /// Movements currently applied, in order.
+    pub fn history(&self) -> &[Step<T>] {
+        &self.steps[..self.position]
+    }
+
+    pub fn top_of_stacks(&self) -> String {
+        self.stacks.top_of_stacks()
+    }
+
+    /// Applies the next movement, returning what it did, or `None` once every
+    /// movement was applied.
+    pub fn redo(&mut self) -> Result<Option<&Step<T>>, MovementError> {
+        let Some(&movement) = self.movements.get(self.position) else {
+            return Ok(None);
+        };
+
+        let error = |reason| MovementError::new(self.position + 1, movement, reason);
+
+        match self.steps.get(self.position) {
+            Some(step) => {
+                self.stacks
+                    .take(movement.origin, step.taken.len())
+                    .map_err(error)?;
+                self.stacks
+                    .put(movement.destination, step.placed.iter().cloned());
+            }
+            None => {
+                self.stacks.check(&movement).map_err(error)?;
+
+                let origin = &self.stacks.stacks[movement.origin - 1];
+                let taken = origin[origin.len() - movement.amount..].to_vec();
+
+                self.model
+                    .apply(&mut self.stacks, movement)
+                    .map_err(error)?;
+
+                let destination = &self.stacks.stacks[movement.destination - 1];
+                let placed = destination[destination.len() - movement.amount..].to_vec();
+
+                self.steps.push(Step {
+                    movement,
+                    taken,
+                    placed,
+                });
+            }
+        }
+
+        self.position += 1;
+
+        Ok(self.steps.get(self.position - 1))
+    }
+
+    /// Reverts the last applied movement, returning what it had done, or `None` if
+    /// nothing was applied.
+    pub fn undo(&mut self) -> Option<&Step<T>> {
+        self.position = self.position.checked_sub(1)?;
+
+        let step = &self.steps[self.position];
+        let destination = &mut self.stacks.stacks[step.movement.destination - 1];
+        destination.truncate(destination.len() - step.placed.len());
+        self.stacks
+            .put(step.movement.origin, step.taken.iter().cloned());
+
+        Some(step)
+    }
+
+    /// Undoes or applies movements until exactly `n` of them are applied, or all of
+    /// them if there are fewer than `n`.
+    pub fn seek(&mut self, n: usize) -> Result<(), MovementError> {
+        let n = n.min(self.movements.len());
+
+        while self.position > n {
+            self.undo();
+        }
+
+        while self.position < n {
+            self.redo()?;
+        }
+
+        Ok(())
+    }
+
+    /// Crates on top of the stacks once the first `n` movements are applied, leaving
+    /// the session where it was.
+    pub fn top_of_stacks_at(&mut self, n: usize) -> Result<String, MovementError> {
+        let position = self.position;
+
+        self.seek(n)?;
+        let top = self.top_of_stacks();
+        self.seek(position)?;
+
+        Ok(top)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::{AlternatingCrane, CrateMover9000},
+        parsing::parse_file_contents,
+    };
+
+    const TEST: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn undo_and_redo() {
+        let (stacks, movements) = parse_file_contents(TEST).unwrap();
+        let initial = stacks.to_string();
+        let mut session = CraneSession::new(CrateMover9000, stacks, movements);
+
+        let step = session.redo().unwrap().unwrap().clone();
+        assert_eq!(step.taken, ['D']);
+        assert_eq!(step.placed, ['D']);
+        assert_eq!(session.top_of_stacks(), "DCP");
+
+        let step = session.redo().unwrap().unwrap().clone();
+        assert_eq!(step.taken, ['Z', 'N', 'D']);
+        assert_eq!(step.placed, ['D', 'N', 'Z']);
+
+        assert_eq!(session.undo().unwrap(), &step);
+        assert_eq!(session.position(), 1);
+        assert_eq!(session.undo().unwrap().movement.amount, 1);
+        assert!(session.undo().is_none());
+        assert_eq!(session.stacks().to_string(), initial);
+
+        session.seek(10).unwrap();
+        assert_eq!(session.position(), 4);
+        assert_eq!(session.top_of_stacks(), "CMZ");
+        assert!(session.redo().unwrap().is_none());
+        assert_eq!(session.history().len(), 4);
+
+        assert_eq!(session.top_of_stacks_at(0).unwrap(), "NDP");
+        assert_eq!(session.top_of_stacks_at(2).unwrap(), "CZ");
+        assert_eq!(session.position(), 4);
+    }
+
+    #[test]
+    fn stops_at_invalid_move() {
+        let (stacks, mut movements) = parse_file_contents(TEST).unwrap();
+        movements.insert(
+            2,
+            Movement {
+                amount: 1,
+                origin: 0,
+                destination: 1,
+            },
+        );
+        let mut session = CraneSession::new(CrateMover9000, stacks, movements);
+
+        let error = session.seek(4).unwrap_err();
+        assert_eq!(error.index, 3);
+        assert_eq!(session.position(), 2);
+        assert_eq!(session.top_of_stacks(), "CZ");
+    }
+
+    #[test]
+    fn replays_recorded_moves() {
+        let (stacks, movements) = parse_file_contents(TEST).unwrap();
+        let mut session = CraneSession::new(AlternatingCrane::default(), stacks, movements);
+
+        session.seek(4).unwrap();
+        let end = session.stacks().to_string();
+
+        // Going back and forth again must not shift which lifts are reversed.
+        session.seek(1).unwrap();
+        session.seek(4).unwrap();
+        assert_eq!(session.stacks().to_string(), end);
+    }
+}
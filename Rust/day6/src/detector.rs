@@ -0,0 +1,189 @@
+use std::io::{self, Read};
+
+use crate::marker::SlidingWindow;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarkerKind {
+    StartOfPacket,
+    StartOfMessage,
+}
+
+impl MarkerKind {
+    /// Number of different characters making up the marker.
+    pub fn size(&self) -> usize {
+        match self {
+            MarkerKind::StartOfPacket => 4,
+            MarkerKind::StartOfMessage => 14,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Marker {
+    pub kind: MarkerKind,
+    /// Number of bytes read from the start of the stream when the marker completed.
+    pub offset: usize,
+}
+
+/// Looks for the first start-of-packet and start-of-message markers of a datastream
+/// that is received a piece at a time.
+#[derive(Clone, Debug)]
+pub struct MarkerDetector {
+    /// Markers not found yet.
+    pending: Vec<(MarkerKind, SlidingWindow)>,
+    offset: usize,
+}
+
+impl Default for MarkerDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkerDetector {
+    pub fn new() -> Self {
+        Self {
+            pending: [MarkerKind::StartOfPacket, MarkerKind::StartOfMessage]
+                .into_iter()
+                .map(|kind| (kind, SlidingWindow::new(kind.size())))
+                .collect(),
+            offset: 0,
+        }
+    }
+
+    /// Number of bytes fed so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether both markers were found, in which case there is no need to feed it
+    /// anymore.
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Feeds the next byte of the stream, returning the markers it completes.
+    pub fn feed(&mut self, byte: u8) -> Vec<Marker> {
+        self.offset += 1;
+
+        let mut found = Vec::new();
+
+        self.pending.retain_mut(|(kind, window)| {
+            let complete = window.push(byte);
+
+            if complete {
+                found.push(Marker {
+                    kind: *kind,
+                    offset: self.offset,
+                });
+            }
+
+            !complete
+        });
+
+        found
+    }
+
+    /// Feeds the next chunk of the stream, returning the markers it completes.
+    pub fn feed_chunk(&mut self, chunk: &[u8]) -> Vec<Marker> {
+        chunk.iter().flat_map(|&byte| self.feed(byte)).collect()
+    }
+
+    /// Reads the stream until both markers are found or it runs out, handing each
+    /// marker to `on_marker` as soon as the chunk completing it is read.
+    pub fn read_from(
+        &mut self,
+        mut reader: impl Read,
+        mut on_marker: impl FnMut(Marker),
+    ) -> io::Result<()> {
+        let mut buffer = [0; 1024];
+
+        while !self.is_done() {
+            let n = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+
+            self.feed_chunk(&buffer[..n])
+                .into_iter()
+                .for_each(&mut on_marker);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, thread};
+
+    use super::*;
+    use crate::marker::find_marker;
+
+    const BUFFER: &str = include_str!("../input.txt");
+
+    #[test]
+    fn byte_by_byte() {
+        let mut detector = MarkerDetector::new();
+        let mut found = Vec::new();
+
+        for byte in "mjqjpqmgbljsphdztnvjfqwrcgsmlb".bytes() {
+            found.extend(detector.feed(byte));
+        }
+
+        assert_eq!(
+            found,
+            [
+                Marker {
+                    kind: MarkerKind::StartOfPacket,
+                    offset: 7
+                },
+                Marker {
+                    kind: MarkerKind::StartOfMessage,
+                    offset: 19
+                }
+            ]
+        );
+        assert!(detector.is_done());
+        assert!(detector.feed(b'a').is_empty());
+    }
+
+    #[test]
+    fn from_reader_and_channel() {
+        let expected = vec![
+            Marker {
+                kind: MarkerKind::StartOfPacket,
+                offset: find_marker(BUFFER, 4).unwrap(),
+            },
+            Marker {
+                kind: MarkerKind::StartOfMessage,
+                offset: find_marker(BUFFER, 14).unwrap(),
+            },
+        ];
+
+        let mut detector = MarkerDetector::new();
+        let mut found = Vec::new();
+        detector
+            .read_from(BUFFER.as_bytes(), |marker| found.push(marker))
+            .unwrap();
+        assert_eq!(found, expected);
+
+        // Chunks of odd sizes, so that markers straddle them.
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for chunk in BUFFER.as_bytes().chunks(7) {
+                sender.send(chunk.to_vec()).unwrap();
+            }
+        });
+
+        let mut detector = MarkerDetector::new();
+        let found: Vec<Marker> = receiver
+            .iter()
+            .flat_map(|chunk| detector.feed_chunk(&chunk))
+            .collect();
+
+        assert_eq!(found, expected);
+    }
+}
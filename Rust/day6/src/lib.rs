@@ -0,0 +1,3 @@
+pub mod detector;
+pub mod marker;
+pub mod segment;
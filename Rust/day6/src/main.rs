@@ -1,32 +1,89 @@
-use std::collections::HashSet;
+use std::{io, num::ParseIntError};
+
+use day6::{
+    detector::MarkerDetector,
+    marker::find_marker,
+    segment::{segment, InvalidWindow},
+};
+use thiserror::Error;
 
 const BUFFER: &str = include_str!("../input.txt");
 
 fn solve_part_one(buffer: &str) -> Option<usize> {
-    buffer
-        .as_bytes()
-        .windows(4)
-        .position(|x| {
-            let set: HashSet<&u8> = HashSet::from_iter(x.iter());
-            set.len() == 4
-        })
-        .map(|x| x + 4)
+    find_marker(buffer, 4)
 }
 
 fn solve_part_two(buffer: &str) -> Option<usize> {
-    buffer
-        .as_bytes()
-        .windows(14)
-        .position(|x| {
-            let set: HashSet<&u8> = HashSet::from_iter(x.iter());
-            set.len() == 14
-        })
-        .map(|x| x + 14)
+    find_marker(buffer, 14)
 }
 
-fn main() {
-    println!("{:?}", solve_part_one(BUFFER));
-    println!("{:?}", solve_part_two(BUFFER));
+fn print_packets(window: &str, json: bool) -> Result<(), ExecutionError> {
+    let buffer = BUFFER.trim_end().as_bytes();
+    let segmentation = segment(buffer, window.parse()?)?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&segmentation).expect("Segmentation is serializable")
+        );
+        return Ok(());
+    }
+
+    for packet in &segmentation.packets {
+        println!(
+            "{}..{} {}: {} bytes",
+            packet.marker.start,
+            packet.marker.end,
+            String::from_utf8_lossy(&buffer[packet.marker.clone()]),
+            packet.payload.len()
+        );
+    }
+
+    println!(
+        "{} markers, longest marker-free run of {} bytes, longest payload of {} bytes",
+        segmentation.stats.n_markers,
+        segmentation.stats.longest_marker_free_run,
+        segmentation.stats.longest_payload
+    );
+
+    Ok(())
+}
+
+fn main() -> Result<(), ExecutionError> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args[..] {
+        // Reads the datastream from the standard input, printing the markers as soon
+        // as they are found.
+        ["stream"] => {
+            let mut detector = MarkerDetector::new();
+
+            detector.read_from(io::stdin().lock(), |marker| {
+                println!("{:?}: {}", marker.kind, marker.offset);
+            })?;
+        }
+        ["packets", window] => print_packets(window, false)?,
+        ["packets", window, "json"] => print_packets(window, true)?,
+        _ => {
+            println!("{:?}", solve_part_one(BUFFER));
+            println!("{:?}", solve_part_two(BUFFER));
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+enum ExecutionError {
+    #[error("Error when reading the datastream")]
+    IoError(#[from] io::Error),
+
+    #[error("Error when parsing the window size")]
+    ParseArgumentError(#[from] ParseIntError),
+
+    #[error("Invalid window size")]
+    InvalidWindow(#[from] InvalidWindow),
 }
 
 #[cfg(test)]
@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+
+/// The last `size` bytes of a datastream, along with how many times each byte shows
+/// up in them and how many different ones there are, so that each byte is only
+/// looked at twice whatever the window size.
+#[derive(Clone, Debug)]
+pub struct SlidingWindow {
+    size: usize,
+    recent: VecDeque<u8>,
+    counts: [usize; 256],
+    distinct: usize,
+}
+
+impl SlidingWindow {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            recent: VecDeque::with_capacity(size + 1),
+            counts: [0; 256],
+            distinct: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Slides the window over the next byte, returning whether the last `size`
+    /// bytes are now all different.
+    pub fn push(&mut self, byte: u8) -> bool {
+        if self.counts[byte as usize] == 0 {
+            self.distinct += 1;
+        }
+        self.counts[byte as usize] += 1;
+        self.recent.push_back(byte);
+
+        if self.recent.len() > self.size {
+            let old = self.recent.pop_front().expect("The window is not empty") as usize;
+
+            self.counts[old] -= 1;
+            if self.counts[old] == 0 {
+                self.distinct -= 1;
+            }
+        }
+
+        self.distinct == self.size
+    }
+
+    /// Empties the window, as if no byte had been pushed yet.
+    pub fn clear(&mut self) {
+        self.recent.clear();
+        self.counts = [0; 256];
+        self.distinct = 0;
+    }
+}
+
+/// Finds the end of the first `window` characters that are all different.
+pub fn find_marker(buffer: &str, window: usize) -> Option<usize> {
+    if window == 0 {
+        return Some(0);
+    }
+
+    let mut sliding_window = SlidingWindow::new(window);
+
+    buffer
+        .bytes()
+        .position(|byte| sliding_window.push(byte))
+        .map(|i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    const BUFFER: &str = include_str!("../input.txt");
+
+    #[test]
+    fn test_find_marker() {
+        assert_eq!(find_marker("abcd", 0), Some(0));
+        assert_eq!(find_marker("aab", 1), Some(1));
+        assert_eq!(find_marker("aab", 2), Some(3));
+        assert_eq!(find_marker("aab", 3), None);
+        assert_eq!(find_marker("", 4), None);
+
+        // Same as checking every window on its own.
+        for window in 1..=26 {
+            let expected = BUFFER
+                .as_bytes()
+                .windows(window)
+                .position(|x| x.iter().collect::<HashSet<_>>().len() == window)
+                .map(|x| x + window);
+
+            assert_eq!(find_marker(BUFFER, window), expected);
+        }
+    }
+}
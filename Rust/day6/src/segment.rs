@@ -0,0 +1,191 @@
+use std::ops::Range;
+
+use serde::{Serialize, Serializer};
+use thiserror::Error;
+
+use crate::marker::SlidingWindow;
+
+/// A marker along with the data following it, up to the next marker.
+///
+/// Markers are made of bytes, so that a packet may start or end in the middle of a
+/// multi-byte character. Payloads are serialized as text, with such partial
+/// characters replaced by U+FFFD.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Packet<'a> {
+    /// Position of the marker's bytes in the datastream.
+    pub marker: Range<usize>,
+    #[serde(serialize_with = "serialize_lossy")]
+    pub payload: &'a [u8],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    pub n_markers: usize,
+    /// Longest stretch of the datastream without any byte of a marker.
+    pub longest_marker_free_run: usize,
+    pub longest_payload: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Segmentation<'a> {
+    /// Data before the first marker.
+    #[serde(serialize_with = "serialize_lossy")]
+    pub preamble: &'a [u8],
+    pub packets: Vec<Packet<'a>>,
+    pub stats: Stats,
+}
+
+/// A marker can't be empty, nor hold more different bytes than there are.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+#[error("Invalid window size {0}, expected between 1 and 256 bytes")]
+pub struct InvalidWindow(pub usize);
+
+const MAX_WINDOW: usize = u8::MAX as usize + 1;
+
+fn serialize_lossy<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&String::from_utf8_lossy(bytes))
+}
+
+/// Finds every marker of `window` different bytes, looking for the next one right
+/// after the end of the previous one so that they don't overlap.
+pub fn find_all_markers(buffer: &[u8], window: usize) -> Result<Vec<Range<usize>>, InvalidWindow> {
+    if window == 0 || window > MAX_WINDOW {
+        return Err(InvalidWindow(window));
+    }
+
+    let mut sliding_window = SlidingWindow::new(window);
+    let mut markers = Vec::new();
+
+    for (i, &byte) in buffer.iter().enumerate() {
+        if sliding_window.push(byte) {
+            markers.push(i + 1 - window..i + 1);
+            sliding_window.clear();
+        }
+    }
+
+    Ok(markers)
+}
+
+/// Splits the datastream into the packets following each marker.
+pub fn segment(buffer: &[u8], window: usize) -> Result<Segmentation<'_>, InvalidWindow> {
+    let markers = find_all_markers(buffer, window)?;
+
+    let preamble_end = markers.first().map_or(buffer.len(), |marker| marker.start);
+
+    let packets: Vec<Packet> = markers
+        .iter()
+        .enumerate()
+        .map(|(i, marker)| {
+            let end = markers.get(i + 1).map_or(buffer.len(), |next| next.start);
+
+            Packet {
+                marker: marker.clone(),
+                payload: &buffer[marker.end..end],
+            }
+        })
+        .collect();
+
+    let stats = Stats {
+        n_markers: packets.len(),
+        longest_marker_free_run: packets
+            .iter()
+            .map(|packet| packet.payload.len())
+            .chain([preamble_end])
+            .max()
+            .unwrap_or(0),
+        longest_payload: packets
+            .iter()
+            .map(|packet| packet.payload.len())
+            .max()
+            .unwrap_or(0),
+    };
+
+    Ok(Segmentation {
+        preamble: &buffer[..preamble_end],
+        packets,
+        stats,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::marker::find_marker;
+
+    const BUFFER: &str = include_str!("../input.txt");
+
+    #[test]
+    fn all_markers() {
+        assert_eq!(
+            find_all_markers(b"abcdabcdaabcd", 4),
+            Ok(vec![0..4, 4..8, 9..13])
+        );
+        assert_eq!(find_all_markers(b"aaaa", 2), Ok(vec![]));
+
+        let markers = find_all_markers(BUFFER.as_bytes(), 14).unwrap();
+        assert_eq!(markers[0].end, find_marker(BUFFER, 14).unwrap());
+        assert!(markers.windows(2).all(|w| w[0].end <= w[1].start));
+    }
+
+    #[test]
+    fn packets() {
+        let segmentation = segment(b"xxabcyyyyzzcab", 3).unwrap();
+
+        assert_eq!(segmentation.preamble, b"x");
+        assert_eq!(
+            segmentation.packets,
+            [
+                Packet {
+                    marker: 1..4,
+                    payload: b"cyyyyz",
+                },
+                Packet {
+                    marker: 10..13,
+                    payload: b"b",
+                }
+            ]
+        );
+        assert_eq!(
+            segmentation.stats,
+            Stats {
+                n_markers: 2,
+                longest_marker_free_run: 6,
+                longest_payload: 6,
+            }
+        );
+
+        let json = serde_json::to_value(segment(b"aabcd", 3).unwrap()).unwrap();
+        assert_eq!(json["packets"][0]["marker"]["start"], 1);
+        assert_eq!(json["packets"][0]["payload"], "d");
+    }
+
+    #[test]
+    fn window_sizes() {
+        assert_eq!(segment(b"abc", 0), Err(InvalidWindow(0)));
+        assert_eq!(segment(b"abc", 257), Err(InvalidWindow(257)));
+
+        // Every byte value once is the largest possible marker.
+        let all_bytes: Vec<u8> = (0..=u8::MAX).collect();
+        assert_eq!(
+            find_all_markers(&all_bytes, 256).unwrap().first(),
+            Some(&(0..256))
+        );
+    }
+
+    #[test]
+    fn non_ascii() {
+        // The marker ends in the middle of the two bytes of 'é'.
+        let segmentation = segment("aé".as_bytes(), 2).unwrap();
+
+        assert_eq!(
+            segmentation.packets,
+            [Packet {
+                marker: 0..2,
+                payload: &[0xa9],
+            }]
+        );
+
+        let json = serde_json::to_value(&segmentation).unwrap();
+        assert_eq!(json["packets"][0]["payload"], "\u{fffd}");
+    }
+}
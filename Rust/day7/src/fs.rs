@@ -1,12 +1,20 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    ops::Index,
+};
+
 use petgraph::{
     prelude::{DiGraph, NodeIndex},
-    visit::EdgeRef,
+    visit::{DfsPostOrder, EdgeRef},
 };
 use thiserror::Error;
 
 pub struct FileSystem<'a> {
     pub fs: DiGraph<FileEntry<'a>, ()>,
     pub current_path: NodeIndex<u32>,
+    /// Entries of each folder by name, so that finding one doesn't go through all
+    /// of its siblings.
+    children: HashMap<NodeIndex, HashMap<&'a str, NodeIndex>>,
 }
 
 impl Default for FileSystem<'_> {
@@ -14,59 +22,159 @@ impl Default for FileSystem<'_> {
         let mut fs = DiGraph::new();
         let current_path = fs.add_node(FileEntry::Folder("/"));
 
-        Self { fs, current_path }
+        Self {
+            fs,
+            current_path,
+            children: HashMap::new(),
+        }
     }
 }
 
-impl FileSystem<'_> {
-    pub fn move_up(&mut self) -> Result<(), MovementError> {
-        match self
-            .fs
-            .edges_directed(self.current_path, petgraph::Direction::Incoming)
-            .next()
-        {
-            Some(edge) => {
-                self.current_path = edge.source();
-                Ok(())
-            }
-            None => Err(MovementError),
+impl<'a> FileSystem<'a> {
+    /// Adds an entry to the current folder, unless it already holds one with the same
+    /// name, as happens when `ls` is run twice in the same folder.
+    pub fn add_entry(&mut self, entry: FileEntry<'a>) {
+        let siblings = self.children.entry(self.current_path).or_default();
+
+        if let Entry::Vacant(vacant) = siblings.entry(entry.get_name()) {
+            let new_node = self.fs.add_node(entry);
+            self.fs.add_edge(self.current_path, new_node, ());
+            vacant.insert(new_node);
         }
     }
 
+    pub fn move_up(&mut self) -> Result<(), MovementError> {
+        self.current_path = self.get_parent(self.current_path).ok_or(MovementError)?;
+
+        Ok(())
+    }
+
     pub fn move_down(&mut self, name: &str) -> Result<(), MovementError> {
         self.current_path = self
-            .fs
-            .edges_directed(self.current_path, petgraph::Direction::Outgoing)
-            .find(|edge| {
-                let tgt = edge.target();
-                self.fs[tgt].get_name() == name
-            })
-            .ok_or(MovementError)?
-            .target();
+            .get_child(self.current_path, name)
+            .ok_or(MovementError)?;
 
         Ok(())
     }
 
-    pub fn get_dir_sizes(&self) -> Vec<usize> {
+    /// Moves to the given path, like `cd` does. Paths starting with `/` are taken
+    /// from the root and the others from the current folder. The current folder is
+    /// left unchanged if the path doesn't exist.
+    pub fn change_directory(&mut self, path: &str) -> Result<(), MovementError> {
+        self.current_path = self.resolve(self.current_path, path).ok_or(MovementError)?;
+
+        Ok(())
+    }
+
+    pub fn root(&self) -> NodeIndex {
+        NodeIndex::new(0)
+    }
+
+    fn get_parent(&self, idx: NodeIndex) -> Option<NodeIndex> {
+        self.fs
+            .edges_directed(idx, petgraph::Direction::Incoming)
+            .next()
+            .map(|edge| edge.source())
+    }
+
+    fn get_child(&self, idx: NodeIndex, name: &str) -> Option<NodeIndex> {
+        self.children.get(&idx)?.get(name).copied()
+    }
+
+    /// Follows a path, segment by segment, from the given entry, or from the root if
+    /// it starts with `/`. Handles `.` and `..`.
+    fn resolve(&self, from: NodeIndex, path: &str) -> Option<NodeIndex> {
+        let start = if path.starts_with('/') {
+            self.root()
+        } else {
+            from
+        };
+
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(start, |idx, name| match name {
+                "." => Some(idx),
+                ".." => self.get_parent(idx),
+                name => self.get_child(idx, name),
+            })
+    }
+
+    /// Finds an entry from its path, such as `/a/e`, starting from the root.
+    pub fn lookup(&self, path: &str) -> Option<NodeIndex> {
+        self.resolve(self.root(), path)
+    }
+
+    /// Absolute path of an entry, which unlike its name identifies it.
+    pub fn get_path(&self, idx: NodeIndex) -> String {
+        let mut names = Vec::new();
+        let mut current = idx;
+
+        while let Some(parent) = self.get_parent(current) {
+            names.push(self.fs[current].get_name());
+            current = parent;
+        }
+
+        names.reverse();
+
+        format!("/{}", names.join("/"))
+    }
+
+    /// Folders, along with their size, in the order they were found.
+    pub fn get_dirs(&self) -> Vec<(NodeIndex, usize)> {
+        let sizes = self.get_sizes();
+
         self.fs
             .node_indices()
             .filter(|idx| matches!(self.fs[*idx], FileEntry::Folder(_)))
-            .map(|idx| self.get_size(idx))
+            .map(|idx| (idx, sizes[idx]))
             .collect()
     }
 
-    fn get_size(&self, idx: NodeIndex) -> usize {
-        match &self.fs[idx] {
-            FileEntry::File(file) => file.size,
-            FileEntry::Folder(_) => self
-                .fs
-                .edges_directed(idx, petgraph::Direction::Outgoing)
-                .map(|e| {
-                    let tgt = e.target();
-                    self.get_size(tgt)
-                })
-                .sum(),
+    /// Computes the size of every entry at once, going through each of them a single
+    /// time.
+    pub fn get_sizes(&self) -> Sizes {
+        let mut sizes = vec![0; self.fs.node_count()];
+        let mut dfs = DfsPostOrder::new(&self.fs, self.root());
+
+        // Children are always visited before their parent.
+        while let Some(idx) = dfs.next(&self.fs) {
+            sizes[idx.index()] = match &self.fs[idx] {
+                FileEntry::File(file) => file.size,
+                FileEntry::Folder(_) => self
+                    .fs
+                    .edges_directed(idx, petgraph::Direction::Outgoing)
+                    .map(|e| sizes[e.target().index()])
+                    .sum(),
+            };
         }
+
+        Sizes(sizes)
+    }
+
+    pub fn get_dir_sizes(&self) -> Vec<usize> {
+        self.get_dirs().into_iter().map(|(_, size)| size).collect()
+    }
+}
+
+/// Size of every entry of a file system, as it was when they were computed.
+pub struct Sizes(Vec<usize>);
+
+impl Sizes {
+    pub fn get(&self, idx: NodeIndex) -> Option<usize> {
+        self.0.get(idx.index()).copied()
+    }
+
+    /// Size of the entry at the given path, see [`FileSystem::lookup`].
+    pub fn get_by_path(&self, fs: &FileSystem, path: &str) -> Option<usize> {
+        self.get(fs.lookup(path)?)
+    }
+}
+
+impl Index<NodeIndex> for Sizes {
+    type Output = usize;
+
+    fn index(&self, idx: NodeIndex) -> &usize {
+        &self.0[idx.index()]
     }
 }
 
@@ -79,12 +187,12 @@ pub enum FileEntry<'a> {
     Folder(&'a str),
 }
 
-impl FileEntry<'_> {
+impl<'a> FileEntry<'a> {
     /// Gets the name associated to an entry.
     ///
     /// Note that multiple entries can have the same name, so don't use the name as
     /// an identifier.
-    fn get_name(&self) -> &str {
+    fn get_name(&self) -> &'a str {
         match self {
             FileEntry::File(file) => file.name,
             FileEntry::Folder(folder_name) => folder_name,
@@ -96,3 +204,77 @@ pub struct File<'a> {
     pub size: usize,
     pub name: &'a str,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::parsing::build_file_system;
+
+    const TEST_INPUT: &str = include_str!("../test_input.txt");
+
+    #[test]
+    fn sizes() {
+        let fs = build_file_system(TEST_INPUT).unwrap();
+        let sizes = fs.get_sizes();
+
+        assert_eq!(sizes[fs.root()], 48381165);
+        assert_eq!(sizes.get_by_path(&fs, "/a"), Some(94853));
+        assert_eq!(sizes.get_by_path(&fs, "/a/e"), Some(584));
+        assert_eq!(sizes.get_by_path(&fs, "a/e/i"), Some(584));
+        assert_eq!(sizes.get_by_path(&fs, "/d/k"), Some(7214296));
+        assert_eq!(sizes.get_by_path(&fs, "/"), Some(48381165));
+        assert_eq!(sizes.get_by_path(&fs, "/a/x"), None);
+    }
+
+    #[test]
+    fn paths() {
+        let mut fs = build_file_system(TEST_INPUT).unwrap();
+
+        let e = fs.lookup("/a/e").unwrap();
+        assert_eq!(fs.get_path(e), "/a/e");
+        assert_eq!(fs.get_path(fs.root()), "/");
+        assert_eq!(fs.lookup("/a/e/../../d/./k"), fs.lookup("/d/k"));
+        assert_eq!(fs.lookup("/.."), None);
+
+        fs.change_directory("/a/e").unwrap();
+        assert_eq!(fs.current_path, e);
+        fs.change_directory("../../d").unwrap();
+        assert_eq!(fs.get_path(fs.current_path), "/d");
+        assert!(fs.change_directory("x/y").is_err());
+        assert_eq!(fs.get_path(fs.current_path), "/d");
+        fs.change_directory("/").unwrap();
+        assert_eq!(fs.current_path, fs.root());
+    }
+
+    #[test]
+    fn deep_tree() {
+        let depth = 5000;
+        let mut contents = String::from("$ cd /\n");
+
+        for _ in 0..depth {
+            contents.push_str("$ ls\n1 f\ndir d\n$ cd d\n");
+        }
+
+        let fs = build_file_system(&contents).unwrap();
+        let sizes = fs.get_dir_sizes();
+
+        assert_eq!(sizes.len(), depth + 1);
+        assert_eq!(sizes.iter().sum::<usize>(), depth * (depth + 1) / 2);
+    }
+
+    #[test]
+    fn wide_tree() {
+        let width = 50_000;
+        let mut contents = String::from("$ cd /\n$ ls\n");
+
+        for i in 0..width {
+            contents.push_str(&format!("1 f{i}\ndir d{i}\n"));
+        }
+        for i in 0..width {
+            contents.push_str(&format!("$ cd d{i}\n$ ls\n2 g\n$ cd ..\n"));
+        }
+
+        let fs = build_file_system(&contents).unwrap();
+
+        assert_eq!(fs.get_sizes()[fs.root()], 3 * width);
+    }
+}
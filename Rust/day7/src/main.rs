@@ -32,8 +32,30 @@ fn solve_part_two(contents: &str) -> Result<usize, ExecutionError> {
 }
 
 fn main() -> Result<(), ExecutionError> {
-    println!("{}", solve_part_one(INPUT)?);
-    println!("{:?}", solve_part_two(INPUT)?);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args[..] {
+        ["size", path] => {
+            let fs = build_file_system(INPUT)?;
+
+            match fs.get_sizes().get_by_path(&fs, path) {
+                Some(size) => println!("{size}"),
+                None => println!("No such file or directory: {path}"),
+            }
+        }
+        ["dirs"] => {
+            let fs = build_file_system(INPUT)?;
+
+            for (idx, size) in fs.get_dirs() {
+                println!("{} {size}", fs.get_path(idx));
+            }
+        }
+        _ => {
+            println!("{}", solve_part_one(INPUT)?);
+            println!("{:?}", solve_part_two(INPUT)?);
+        }
+    }
 
     Ok(())
 }
@@ -6,25 +6,20 @@ use crate::fs::{File, FileEntry, FileSystem, MovementError};
 
 enum ParsingResult<'a> {
     FileEntry(FileEntry<'a>),
-    MoveDown(&'a str),
-    MoveUp,
+    ChangeDirectory(&'a str),
     DoNothing,
 }
 
-pub fn build_file_system(contents: &str) -> Result<FileSystem, ExecutionError> {
-    let parsed_lines = contents.lines().skip(1).map(parse_line);
+pub fn build_file_system(contents: &str) -> Result<FileSystem<'_>, ExecutionError> {
+    let parsed_lines = contents.lines().map(parse_line);
 
     let mut fs = FileSystem::default();
     for parsed_line in parsed_lines {
         let parsed_line = parsed_line?;
 
         match parsed_line {
-            ParsingResult::FileEntry(file_entry) => {
-                let new_node = fs.fs.add_node(file_entry);
-                fs.fs.add_edge(fs.current_path, new_node, ());
-            }
-            ParsingResult::MoveDown(name) => fs.move_down(name)?,
-            ParsingResult::MoveUp => fs.move_up()?,
+            ParsingResult::FileEntry(file_entry) => fs.add_entry(file_entry),
+            ParsingResult::ChangeDirectory(path) => fs.change_directory(path)?,
             ParsingResult::DoNothing => (),
         }
     }
@@ -32,13 +27,12 @@ pub fn build_file_system(contents: &str) -> Result<FileSystem, ExecutionError> {
     Ok(fs)
 }
 
-fn parse_line(line: &str) -> Result<ParsingResult, ParseError> {
+fn parse_line(line: &str) -> Result<ParsingResult<'_>, ParseError> {
     let line: Vec<&str> = line.split(' ').collect();
 
     match line[..] {
         ["dir", folder_name] => Ok(ParsingResult::FileEntry(FileEntry::Folder(folder_name))),
-        ["$", "cd", ".."] => Ok(ParsingResult::MoveUp),
-        ["$", "cd", name] => Ok(ParsingResult::MoveDown(name)),
+        ["$", "cd", path] => Ok(ParsingResult::ChangeDirectory(path)),
         ["$", "ls"] => Ok(ParsingResult::DoNothing),
         [size, name] => Ok(ParsingResult::FileEntry(FileEntry::File(File {
             size: size.parse()?,
@@ -61,3 +55,30 @@ pub enum ExecutionError {
     ParseError(#[from] ParseError),
     MovementError(#[from] MovementError),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute_and_nested_paths() {
+        let contents = "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\ndir b\n$ cd b\n$ ls\n10 f\n\
+            $ cd /\n$ ls\n20 g\n$ cd a/b\n$ ls\ndir c\n$ cd c\n$ ls\n5 h";
+        let fs = build_file_system(contents).unwrap();
+        let sizes = fs.get_sizes();
+
+        assert_eq!(sizes[fs.root()], 35);
+        assert_eq!(sizes.get_by_path(&fs, "/a/b"), Some(15));
+        assert_eq!(sizes.get_by_path(&fs, "/a/b/c/h"), Some(5));
+
+        assert!(build_file_system("$ cd /\n$ cd x").is_err());
+    }
+
+    #[test]
+    fn repeated_listing() {
+        let fs = build_file_system("$ cd /\n$ ls\n10 f\n$ cd /\n$ ls\n10 f").unwrap();
+
+        assert_eq!(fs.get_sizes()[fs.root()], 10);
+        assert_eq!(fs.fs.node_count(), 2);
+    }
+}